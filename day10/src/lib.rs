@@ -1,29 +1,50 @@
-use std::fs;
-use std::io;
-use std::path::Path;
-use std::str::FromStr;
+#![cfg_attr(test, feature(test))]
 
-pub const INPUT_FILE: &str = "input.txt";
+#[cfg(test)]
+extern crate test;
 
+use aoc::aux::Grid;
+use aoc::aux::geometry::Direction;
+use aoc::error::ParseError;
+use aoc::puzzle::Puzzle;
 
-pub fn read_input(path: impl AsRef<Path>) -> io::Result<String> {
-    fs::read_to_string(path)
-}
+pub mod part1;
+pub mod part2;
+
+pub use part1::solve_part1;
+pub use part2::solve_part2;
+
+
+pub struct Day10;
+
+impl Puzzle for Day10 {
+    type Input = PuzzleInput;
+    type Output1 = i64;
+    type Output2 = i64;
+    type ParseError = ParseError;
+
+    fn parse(input: &str) -> Result<PuzzleInput, Self::ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &PuzzleInput) -> i64 {
+        solve_part1(input)
+    }
 
-pub fn write_output(output: impl ToString) {
-    println!("{}", output.to_string());
+    fn part2(input: &PuzzleInput) -> i64 {
+        solve_part2(input)
+    }
 }
 
-pub fn parse_input(input: &str) -> PuzzleInput {
-    let map = input.lines().map(|x| {
-        x.as_bytes().iter().map(|b| Pipe::from_char(b).unwrap()).collect()
-    }).collect();
-    PuzzleInput { map }
+
+pub fn parse_input(input: &str) -> Result<PuzzleInput, ParseError> {
+    let map = Grid::parse(input, Pipe::EXPECTED, |c| u8::try_from(c).ok().and_then(|b| Pipe::from_char(&b)))?;
+    Ok(PuzzleInput { map })
 }
 
 #[derive(Clone, Debug)]
 pub struct PuzzleInput {
-    pub map: Vec<Vec<Pipe>>,
+    pub map: Grid<Pipe>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -32,6 +53,26 @@ pub enum Pipe {
 }
 
 impl Pipe {
+    pub const EXPECTED: &'static str = "one of `S.|-LJF7`";
+
+    /// The directions in which a pipe connects, in the order of `Direction::ALL`. Empty for the start, whose shape is unknown.
+    pub fn connections(&self) -> &'static [Direction] {
+        match self {
+            Pipe::START | Pipe::GROUND => &[],
+            Pipe::NS => &[Direction::Up, Direction::Down],
+            Pipe::WE => &[Direction::Left, Direction::Right],
+            Pipe::NE => &[Direction::Up, Direction::Right],
+            Pipe::NW => &[Direction::Up, Direction::Left],
+            Pipe::SE => &[Direction::Down, Direction::Right],
+            Pipe::SW => &[Direction::Down, Direction::Left],
+        }
+    }
+
+    pub fn from_connections(connections: &[Direction]) -> Option<Pipe> {
+        [Pipe::NS, Pipe::WE, Pipe::NE, Pipe::NW, Pipe::SE, Pipe::SW].into_iter()
+            .find(|pipe| pipe.connections() == connections)
+    }
+
     pub fn from_char(c: &u8) -> Option<Pipe> {
         match c {
             b'S' => Some(Pipe::START),
@@ -54,19 +95,14 @@ pub struct Coordinate {
 }
 
 
-
 pub fn find_start(input: &PuzzleInput) -> Coordinate {
-    for (y, row) in input.map.iter().enumerate() {
-        if let Some(x) = row.iter().position(|p| *p == Pipe::START) {
-            return Coordinate {y, x};
-        }
-    }
-    panic!("No start found");
+    let (y, x) = input.map.position(|p| *p == Pipe::START).expect("No start found");
+    Coordinate {y, x}
 }
 
 pub fn transition(input: &PuzzleInput, current: &Coordinate, previous: Option<&Coordinate>) -> Coordinate {
     let (y, x) = (current.y, current.x);
-    let current_pipe = &input.map[y][x];
+    let current_pipe = &input.map[(y, x)];
     match current_pipe {
         Pipe::GROUND => panic!("Unexpected ground"),
         Pipe::START => {
@@ -74,14 +110,14 @@ pub fn transition(input: &PuzzleInput, current: &Coordinate, previous: Option<&C
                 panic!("Start should not have previous position")
             } else {
                 let (n1, _n2) = find_neighbours(input, current);
-                return n1.unwrap();
+                n1.unwrap()
             }
         }
         _ => {
             let (n1, n2) = find_neighbours(input, current);
             let (n1, n2) = (n1.unwrap(), n2.unwrap());
             if let Some(previous) = previous {
-                return if n1 == *previous { n2 } else if n2 == *previous { n1 } else { panic!("Previous position not found") };
+                if n1 == *previous { n2 } else if n2 == *previous { n1 } else { panic!("Previous position not found") }
             } else {
                 panic!("Previous position not found")
             }
@@ -91,70 +127,32 @@ pub fn transition(input: &PuzzleInput, current: &Coordinate, previous: Option<&C
 
 fn find_neighbours(input: &PuzzleInput, coo: &Coordinate) -> (Option<Coordinate>, Option<Coordinate>) {
     let (y, x) = (coo.y, coo.x);
-    let height = input.map.len();
-    let width = input.map[0].len();
-    let pipe = &input.map[y][x];
-    let candidates: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-    let candidates = candidates.iter().filter(|(dy, dx)| {
-        let (other_y, other_x) = (y as isize + dy, x as isize + dx);
-        (0 <= other_y) && (other_y < height as isize) && (0 <= other_x) && (other_x < width as isize)
-    });
-    let candidates = candidates.filter(|(dy, dx)| {
-        let (dy, dx) = (*dy, *dx);
-        match pipe {
-            Pipe::NS => (dx == 0) && ((dy == -1) || (dy == 1)),
-            Pipe::WE => (dy == 0) && ((dx == -1) || (dx == 1)),
-            Pipe::NE => match (dy, dx) { (-1, 0) | (0, 1) => true, _ => false },
-            Pipe::NW => match (dy, dx) { (-1, 0) | (0, -1) => true, _ => false },
-            Pipe::SE => match (dy, dx) { (1, 0) | (0, 1) => true, _ => false },
-            Pipe::SW => match (dy, dx) { (1, 0) | (0, -1) => true, _ => false },
-            Pipe::START => {
-                let other_coo = Coordinate { y: (y as isize + dy) as usize, x: (x as isize + dx) as usize };
-                let (other_n1, other_n2) = find_neighbours(input, &other_coo);
-                (other_n1 == Some(coo.clone())) || (other_n2 == Some(coo.clone()))
-            }
-            _ => false,
-        }
-    });
-    let mut candidates = candidates
-        .map(|(dy, dx)| ((y as isize + dy) as usize, (x as isize + dx) as usize))
+    let mut candidates = connected_directions(input, coo)
+        .filter_map(|direction| input.map.step((y, x), direction))
         .map(|(y, x)| Coordinate { y, x });
     (candidates.next(), candidates.next())
 }
 
-
-/// Parse a whitespace-separated list of numbers
-/// 
-/// # Arguments
-/// 
-/// * `numbers` - A string containing a whitespace-separated list of numbers
-/// 
-/// # Returns
-/// 
-/// A vector of parsed numbers
-/// 
-/// # Example
-/// 
-/// ```
-/// let numbers = "1 2 3 4 5";
-/// let parsed = day10::parse_numbers(numbers);
-/// assert_eq!(parsed, Ok(vec![1, 2, 3, 4, 5]));
-/// ```
-pub fn parse_numbers<T: FromStr>(numbers: &str) -> Result<Vec<T>, <T as FromStr>::Err> {
-    numbers
-        .split_whitespace()
-        .map(|x| x.parse())
-        .collect()
+/// The directions in which the pipe at `coo` connects to a neighbour. For the start, these are the neighbours that connect back to it.
+pub fn connected_directions<'a>(input: &'a PuzzleInput, coo: &'a Coordinate) -> impl Iterator<Item = Direction> + 'a {
+    let pipe = &input.map[(coo.y, coo.x)];
+    Direction::ALL.into_iter().filter(move |&direction| match pipe {
+        Pipe::START => input.map.step((coo.y, coo.x), direction)
+            .is_some_and(|other| input.map[other].connections().contains(&direction.reverse())),
+        _ => pipe.connections().contains(&direction),
+    })
 }
 
+
 #[cfg(test)]
 mod tests {
+    use aoc::aux::read_input;
     use super::*;
 
     #[test]
     fn test_find_neighbours() {
         let input = read_input("test_input.txt").unwrap();
-        let input = parse_input(&input);
+        let input = parse_input(&input).unwrap();
 
         let start = Coordinate { y: 2, x: 0 };
         let neighbours = find_neighbours(&input, &start);
@@ -180,4 +178,13 @@ mod tests {
         ];
         assert!(correct.contains(&neighbours.0) && correct.contains(&neighbours.1));
     }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("..F7.\n.FJ|.\nSJ.LX\n").unwrap_err();
+        assert_eq!(err, ParseError::new(3, 5, "X", Pipe::EXPECTED));
+
+        let err = parse_input("..F7.\n.FJ|\nSJ.L7\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.snippet.as_str()), (2, 5, ""));
+    }
 }
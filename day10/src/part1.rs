@@ -0,0 +1,45 @@
+use crate::{PuzzleInput, Coordinate, find_start, transition};
+
+
+pub fn solve_part1(input: &PuzzleInput) -> i64 {
+    let start = find_start(input);
+    let mut current = start.clone();
+    let mut previous: Option<Coordinate> = None;
+    let mut steps = 0;
+    while previous.is_none() || current != start {
+        let next = transition(input, &current, previous.as_ref());
+        (current, previous) = (next, Some(current));
+        steps += 1;
+    }
+    steps / 2
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::{read_input, INPUT_FILE};
+    use aoc::puzzle::read_and_solve_part1;
+    use crate::{parse_input, Day10};
+    use super::*;
+
+    const TEST_SOLUTION: i64 = 8;
+
+    #[test]
+    fn test_find_start() {
+        let input = read_input("test_input.txt").unwrap();
+        let input = parse_input(&input).unwrap();
+        let start = find_start(&input);
+        assert_eq!(start, Coordinate { y: 2, x: 0 });
+    }
+
+    #[test]
+    fn test_part1() {
+        let test_output = read_and_solve_part1::<Day10>("test_input.txt");
+        assert_eq!(test_output, TEST_SOLUTION);
+    }
+
+    #[bench]
+    fn bench_part1(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part1::<Day10>(INPUT_FILE));
+    }
+}
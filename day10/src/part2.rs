@@ -0,0 +1,67 @@
+use crate::{PuzzleInput, Pipe, Coordinate, connected_directions, find_start, transition};
+
+
+pub fn solve_part2(input: &PuzzleInput) -> i64 {
+    let start = find_start(input);
+    let cycle = find_cycle(input, &start);
+    let mut simplified_map = input.map.map(|_| Pipe::GROUND);
+    for coo in cycle.iter() {
+        simplified_map[(coo.y, coo.x)] = input.map[(coo.y, coo.x)].clone();
+    }
+    simplified_map[(start.y, start.x)] = infer_start(input, &start);
+    let mut count = 0;
+    for row in simplified_map.rows() {
+        let mut inside = false;
+        for pipe in row.iter() {
+            if (*pipe == Pipe::GROUND) && inside {
+                count += 1;
+            } else if [Pipe::NS, Pipe::NW, Pipe::NE].contains(pipe) {
+                inside = !inside;
+            }
+        }
+    }
+    count
+}
+
+fn find_cycle(input: &PuzzleInput, start: &Coordinate) -> Vec<Coordinate> {
+    let mut cycle: Vec<Coordinate> = vec![start.clone()];
+    loop {
+        let current = cycle.last().unwrap();
+        let previous = if cycle.len() >= 2 { Some(cycle[cycle.len() - 2].clone()) } else { None };
+        let next = transition(input, current, previous.as_ref());
+        cycle.push(next.clone());
+        if next == *start {
+            return cycle;
+        }
+    }
+}
+
+fn infer_start(input: &PuzzleInput, start: &Coordinate) -> Pipe {
+    let connections: Vec<_> = connected_directions(input, start).collect();
+    Pipe::from_connections(&connections).expect("Invalid start pipe")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::INPUT_FILE;
+    use aoc::puzzle::read_and_solve_part2;
+    use crate::Day10;
+
+    const TEST_SOLUTION_2: i64 = 8;
+    const TEST_SOLUTION_3: i64 = 10;
+
+    #[test]
+    fn test_part2() {
+        let test_output = read_and_solve_part2::<Day10>("test_input2.txt");
+        assert_eq!(test_output, TEST_SOLUTION_2);
+
+        let test_output = read_and_solve_part2::<Day10>("test_input3.txt");
+        assert_eq!(test_output, TEST_SOLUTION_3);
+    }
+
+    #[bench]
+    fn bench_part2(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part2::<Day10>(INPUT_FILE));
+    }
+}
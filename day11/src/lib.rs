@@ -1,37 +1,60 @@
-use std::fs;
-use std::io;
-use std::path::Path;
-use std::str::FromStr;
-use std::vec;
+#![cfg_attr(test, feature(test))]
 
-pub const INPUT_FILE: &str = "input.txt";
+#[cfg(test)]
+extern crate test;
 
+use itertools::Itertools;
 
-pub fn read_input(path: impl AsRef<Path>) -> io::Result<String> {
-    fs::read_to_string(path)
-}
+use aoc::aux::parse_char_map;
+use aoc::error::ParseError;
+use aoc::puzzle::Puzzle;
 
-pub fn write_output(output: impl ToString) {
-    println!("{}", output.to_string());
-}
+pub mod part1;
+pub mod part2;
+pub mod universe;
+
+pub use part1::solve_part1;
+pub use part2::solve_part2;
+pub use universe::{Expansion, Universe};
+
+
+pub struct Day11;
 
-pub fn parse_input(input: &str) -> PuzzleInput {
-    let mut galaxies = vec![];
-    let lines: Vec<&str> = input.lines().collect();
-    let height = lines.len();
-    let width = lines[0].len();
-    for (y, line) in lines.iter().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            if c == '#' {
-                galaxies.push(Coordinate { y, x });
-            }
-        }
+impl Puzzle for Day11 {
+    type Input = PuzzleInput;
+    type Output1 = i64;
+    type Output2 = i64;
+    type ParseError = ParseError;
+
+    fn parse(input: &str) -> Result<PuzzleInput, Self::ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &PuzzleInput) -> i64 {
+        solve_part1(input)
     }
-    PuzzleInput {
-        galaxies: galaxies.into_boxed_slice(),
+
+    fn part2(input: &PuzzleInput) -> i64 {
+        solve_part2(input)
+    }
+}
+
+
+pub fn parse_input(input: &str) -> Result<PuzzleInput, ParseError> {
+    let (entries, [height, width]) = parse_char_map(input, "`.` or `#`", |c| match c {
+        '.' => Some(false),
+        '#' => Some(true),
+        _ => None,
+    })?;
+    let galaxies = entries.iter()
+        .positions(|&is_galaxy| is_galaxy)
+        .map(|i| Coordinate { y: i / width, x: i % width })
+        .collect();
+    Ok(PuzzleInput {
+        galaxies,
         height,
         width,
-    }
+    })
 }
 
 #[derive(Clone, Debug)]
@@ -46,28 +69,3 @@ pub struct Coordinate {
     pub y: usize,
     pub x: usize,
 }
-
-
-/// Parse a whitespace-separated list of numbers
-/// 
-/// # Arguments
-/// 
-/// * `numbers` - A string containing a whitespace-separated list of numbers
-/// 
-/// # Returns
-/// 
-/// A vector of parsed numbers
-/// 
-/// # Example
-/// 
-/// ```
-/// let numbers = "1 2 3 4 5";
-/// let parsed = day11::parse_numbers(numbers);
-/// assert_eq!(parsed, Ok(vec![1, 2, 3, 4, 5]));
-/// ```
-pub fn parse_numbers<T: FromStr>(numbers: &str) -> Result<Vec<T>, <T as FromStr>::Err> {
-    numbers
-        .split_whitespace()
-        .map(|x| x.parse())
-        .collect()
-}
@@ -0,0 +1,29 @@
+use aoc::aux::numeric::Checked;
+
+use crate::{PuzzleInput, Universe};
+
+
+pub fn solve_part1(input: &PuzzleInput) -> i64 {
+    Universe::new(input, Checked(2)).sum_of_distances().0
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::INPUT_FILE;
+    use aoc::puzzle::read_and_solve_part1;
+    use crate::Day11;
+
+    const TEST_SOLUTION: i64 = 374;
+
+    #[test]
+    fn test_part1() {
+        let test_output = read_and_solve_part1::<Day11>("test_input.txt");
+        assert_eq!(test_output, TEST_SOLUTION);
+    }
+
+    #[bench]
+    fn bench_part1(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part1::<Day11>(INPUT_FILE));
+    }
+}
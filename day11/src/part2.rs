@@ -0,0 +1,78 @@
+use aoc::aux::numeric::{Checked, Integer};
+
+use crate::{PuzzleInput, Universe};
+
+
+pub const EMPTY_MULTIPLIER: i64 = 1_000_000;
+
+
+pub fn solve_part2(input: &PuzzleInput) -> i64 {
+    solve(input, Checked(EMPTY_MULTIPLIER)).0
+}
+
+/// The sum of the distances between all pairs of galaxies, when every empty row and column is replaced by `empty_multiplier` of them,
+/// computed in `T`.
+pub fn solve<T: Integer>(input: &PuzzleInput, empty_multiplier: T) -> T {
+    Universe::new(input, empty_multiplier).sum_of_distances()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use aoc::aux::{read_input, INPUT_FILE};
+    use aoc::aux::numeric::BigInt;
+    use crate::parse_input;
+    use super::*;
+
+    const TEST_MULTIPLIER_0: i64 = 2;
+    const TEST_SOLUTION_0: i64 = 374;
+
+    const TEST_MULTIPLIER_1: i64 = 10;
+    const TEST_SOLUTION_1: i64 = 1030;
+
+    const TEST_MULTIPLIER_2: i64 = 100;
+    const TEST_SOLUTION_2: i64 = 8410;
+
+    fn read_and_solve(file_path: impl AsRef<Path>, empty_multiplier: i64) -> i64 {
+        let input = read_input(file_path).unwrap();
+        let input = parse_input(&input).unwrap();
+        solve(&input, empty_multiplier)
+    }
+
+    #[test]
+    fn test_numeric_types() {
+        let input = parse_input(&read_input(INPUT_FILE).unwrap()).unwrap();
+        let solution = solve(&input, EMPTY_MULTIPLIER);
+        assert_eq!(solve(&input, EMPTY_MULTIPLIER as i128), solution as i128);
+        assert_eq!(solve(&input, Checked(EMPTY_MULTIPLIER)), Checked(solution));
+        assert_eq!(solve(&input, BigInt::from(EMPTY_MULTIPLIER)), BigInt::from(solution));
+
+        // The distances are linear in the multiplier, and with a multiplier of 10¹⁴, their sum doesn't fit in an `i64`
+        let multiplier: i64 = 100_000_000_000_000;
+        let exact = solve(&input, BigInt::from(multiplier));
+        let slope = solve(&input, BigInt::from(2)) - solve(&input, BigInt::from(1));
+        assert_eq!(exact, solve(&input, BigInt::from(1)) + slope * BigInt::from(multiplier - 1));
+        assert!(exact > BigInt::from(i64::MAX));
+        assert_eq!(BigInt::from(solve(&input, Checked(multiplier as i128)).0), exact);
+        assert!(std::panic::catch_unwind(|| solve(&input, Checked(multiplier))).is_err());
+    }
+
+    #[test]
+    fn test_part2() {
+        let test_output = read_and_solve("test_input.txt", TEST_MULTIPLIER_0);
+        assert_eq!(test_output, TEST_SOLUTION_0);
+        
+        let test_output = read_and_solve("test_input.txt", TEST_MULTIPLIER_1);
+        assert_eq!(test_output, TEST_SOLUTION_1);
+
+        let test_output = read_and_solve("test_input.txt", TEST_MULTIPLIER_2);
+        assert_eq!(test_output, TEST_SOLUTION_2);
+    }
+
+    #[bench]
+    fn bench_part2(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve(INPUT_FILE, EMPTY_MULTIPLIER));
+    }
+}
@@ -0,0 +1,348 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use aoc::aux::sum_pairwise_manhattan;
+use aoc::aux::numeric::Integer;
+
+use crate::PuzzleInput;
+
+
+/// How the empty rows, or the empty columns, of the universe expand.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Expansion<T> {
+    /// The number of rows (or columns) that an empty row (or column) becomes
+    pub multiplier: T,
+    /// Overrides of `multiplier` for single gaps, keyed by the index of the gap among the empty rows (or columns), from 0
+    pub gaps: HashMap<usize, T>,
+}
+
+impl<T: Integer> Expansion<T> {
+    /// Expand every gap by `multiplier`.
+    pub fn uniform(multiplier: T) -> Expansion<T> {
+        Expansion { multiplier, gaps: HashMap::new() }
+    }
+
+    /// Expand the gap with index `gap` by `multiplier` instead.
+    pub fn with_gap(mut self, gap: usize, multiplier: T) -> Expansion<T> {
+        self.gaps.insert(gap, multiplier);
+        self
+    }
+
+    pub fn multiplier_of(&self, gap: usize) -> T {
+        self.gaps.get(&gap).unwrap_or(&self.multiplier).clone()
+    }
+}
+
+
+/// One axis of the universe: which of its lines are empty, and where every line ends up after the expansion.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Axis<T> {
+    len: usize,
+    /// The original indices of the empty lines
+    empty: Vec<usize>,
+    /// The number of lines that each empty line becomes
+    multipliers: Vec<T>,
+    /// The number of lines added by the first `k` empty lines, for every `k` up to the number of empty lines
+    added_before: Vec<T>,
+}
+
+impl<T: Integer> Axis<T> {
+    fn new(len: usize, occupied: impl Iterator<Item = usize>, expansion: &Expansion<T>) -> Axis<T> {
+        let mut is_empty = vec![true; len];
+        for i in occupied {
+            is_empty[i] = false;
+        }
+        let empty: Vec<usize> = (0..len).filter(|&i| is_empty[i]).collect();
+        let multipliers: Vec<T> = (0..empty.len()).map(|gap| expansion.multiplier_of(gap)).collect();
+        let mut added_before = vec![T::zero()];
+        for multiplier in &multipliers {
+            let added = added_before.last().unwrap().clone() + multiplier.clone() - T::one();
+            added_before.push(added);
+        }
+        Axis { len, empty, multipliers, added_before }
+    }
+
+    /// The first expanded line that original line `i` becomes; `i` may be `len`, for the end of the axis.
+    fn to_expanded(&self, i: usize) -> T {
+        let n_empty = self.empty.partition_point(|&e| e < i);
+        T::from(i as i64) + self.added_before[n_empty].clone()
+    }
+
+    /// The number of expanded lines that original line `i` becomes.
+    fn size(&self, i: usize) -> T {
+        match self.empty.binary_search(&i) {
+            Ok(gap) => self.multipliers[gap].clone(),
+            Err(_) => T::one(),
+        }
+    }
+
+    /// The original line that contains expanded line `e`, if it is within the axis.
+    fn to_original(&self, e: &T) -> Option<usize> {
+        if *e < T::zero() || *e >= self.to_expanded(self.len) {
+            return None;
+        }
+        // The last line that starts at or before `e`, which is never 0-sized, since the next line starts at the same place
+        let (mut lo, mut hi) = (0, self.len);
+        while hi - lo > 1 {
+            let mid = (lo + hi) / 2;
+            if self.to_expanded(mid) <= *e { lo = mid } else { hi = mid }
+        }
+        (*e < self.to_expanded(lo) + self.size(lo)).then_some(lo)
+    }
+}
+
+
+/// The galaxies at their positions `[y, x]` after the empty rows and columns have expanded.
+///
+/// Galaxies are numbered from 0, in reading order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Universe<T> {
+    galaxies: Vec<[T; 2]>,
+    original_galaxies: Vec<[usize; 2]>,
+    rows: Axis<T>,
+    cols: Axis<T>,
+}
+
+impl<T: Integer> Universe<T> {
+    /// Replace every empty row and column by `empty_multiplier` of them.
+    pub fn new(input: &PuzzleInput, empty_multiplier: T) -> Universe<T> {
+        let expansion = Expansion::uniform(empty_multiplier);
+        Universe::with_expansion(input, &expansion, &expansion)
+    }
+
+    /// Expand the empty rows according to `rows`, and the empty columns according to `cols`.
+    pub fn with_expansion(input: &PuzzleInput, rows: &Expansion<T>, cols: &Expansion<T>) -> Universe<T> {
+        let rows = Axis::new(input.height, input.galaxies.iter().map(|galaxy| galaxy.y), rows);
+        let cols = Axis::new(input.width, input.galaxies.iter().map(|galaxy| galaxy.x), cols);
+        let original_galaxies: Vec<[usize; 2]> = input.galaxies.iter().map(|galaxy| [galaxy.y, galaxy.x]).collect();
+        let galaxies = original_galaxies.iter().map(|&[y, x]| [rows.to_expanded(y), cols.to_expanded(x)]).collect();
+        Universe { galaxies, original_galaxies, rows, cols }
+    }
+
+    /// The size `[height, width]` of the expanded universe.
+    pub fn shape(&self) -> [T; 2] {
+        [self.rows.to_expanded(self.rows.len), self.cols.to_expanded(self.cols.len)]
+    }
+
+    /// Where position `[y, x]` of the original universe ends up; an empty line ends up at the first line that it becomes.
+    pub fn to_expanded(&self, [y, x]: [usize; 2]) -> [T; 2] {
+        [self.rows.to_expanded(y), self.cols.to_expanded(x)]
+    }
+
+    /// The position of the original universe that position `[y, x]` of the expanded universe came from, if it is within the universe.
+    pub fn to_original(&self, [y, x]: &[T; 2]) -> Option<[usize; 2]> {
+        Some([self.rows.to_original(y)?, self.cols.to_original(x)?])
+    }
+
+    pub fn galaxies(&self) -> &[[T; 2]] {
+        &self.galaxies
+    }
+
+    pub fn len(&self) -> usize {
+        self.galaxies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.galaxies.is_empty()
+    }
+
+    /// The length of the shortest path between galaxies `a` and `b`.
+    pub fn distance(&self, a: usize, b: usize) -> T {
+        let [ay, ax] = self.galaxies[a].clone();
+        let [by, bx] = self.galaxies[b].clone();
+        ay.distance(by) + ax.distance(bx)
+    }
+
+    /// The sum of the distances between all pairs of galaxies.
+    pub fn sum_of_distances(&self) -> T {
+        sum_pairwise_manhattan(&self.galaxies)
+    }
+
+    /// The galaxy closest to `galaxy`, other than itself, and its distance; the first one in reading order on a tie.
+    pub fn nearest(&self, galaxy: usize) -> Option<(usize, T)> {
+        (0..self.len())
+            .filter(|&other| other != galaxy)
+            .map(|other| (other, self.distance(galaxy, other)))
+            .min_by(|(_, d1), (_, d2)| d1.cmp(d2))
+    }
+
+    /// Two galaxies that are farthest apart, and their distance.
+    pub fn farthest_pair(&self) -> Option<(usize, usize, T)> {
+        // With u = y + x and v = y - x, the distance is max(|Δu|, |Δv|), so the farthest pair is an extreme in u or in v
+        let (mut best, mut best_distance) = (None, T::zero());
+        let rotations: [fn(&[T; 2]) -> T; 2] = [
+            |[y, x]| y.clone() + x.clone(),
+            |[y, x]| y.clone() - x.clone(),
+        ];
+        for rotation in rotations {
+            let values: Vec<T> = self.galaxies.iter().map(rotation).collect();
+            let min = (0..self.len()).min_by(|&i, &j| values[i].cmp(&values[j]))?;
+            let max = (0..self.len()).max_by(|&i, &j| values[i].cmp(&values[j]))?;
+            let distance = values[max].clone() - values[min].clone();
+            if best.is_none() || distance > best_distance {
+                (best, best_distance) = (Some((min.min(max), min.max(max))), distance);
+            }
+        }
+        best.filter(|(a, b)| a != b).map(|(a, b)| (a, b, best_distance))
+    }
+}
+
+/// The expanded map, with a line per row; only sensible for small multipliers.
+impl<T: Integer> fmt::Display for Universe<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let galaxies: HashSet<[usize; 2]> = self.original_galaxies.iter().copied().collect();
+        let [height, width] = self.shape();
+        let mut y = T::zero();
+        while y < height {
+            let mut x = T::zero();
+            while x < width {
+                let original = self.to_original(&[y.clone(), x.clone()]).expect("Position is within the universe");
+                write!(f, "{}", if galaxies.contains(&original) { '#' } else { '.' })?;
+                x = x + T::one();
+            }
+            writeln!(f)?;
+            y = y + T::one();
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::{read_input, INPUT_FILE, TEST_INPUT_FILE};
+    use aoc::aux::numeric::{BigInt, Checked};
+    use crate::parse_input;
+    use super::*;
+
+    fn read_universe(file_path: &str, empty_multiplier: i64) -> Universe<i64> {
+        Universe::new(&parse_input(&read_input(file_path).unwrap()).unwrap(), empty_multiplier)
+    }
+
+    #[test]
+    fn test_queries() {
+        // The galaxies are numbered from 1 in the puzzle
+        let universe = read_universe(TEST_INPUT_FILE, 2);
+        assert_eq!(universe.len(), 9);
+        assert_eq!(universe.distance(4, 8), 9);
+        assert_eq!(universe.distance(0, 6), 15);
+        assert_eq!(universe.distance(2, 5), 17);
+        assert_eq!(universe.distance(7, 8), 5);
+        assert_eq!(universe.galaxies()[0], [0, 4]);
+        assert_eq!(universe.nearest(7), Some((8, 5)));
+        assert_eq!(universe.sum_of_distances(), 374);
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        for multiplier in [1, 2, 10, 1_000_000] {
+            for file_path in [TEST_INPUT_FILE, INPUT_FILE] {
+                let universe = read_universe(file_path, multiplier);
+                let pairs = || (0..universe.len()).flat_map(|a| (a + 1..universe.len()).map(move |b| (a, b)));
+                assert_eq!(universe.sum_of_distances(), pairs().map(|(a, b)| universe.distance(a, b)).sum());
+                let (a, b, distance) = universe.farthest_pair().unwrap();
+                assert_eq!(universe.distance(a, b), distance);
+                assert_eq!(pairs().map(|(a, b)| universe.distance(a, b)).max(), Some(distance));
+                for galaxy in 0..universe.len().min(20) {
+                    let (other, distance) = universe.nearest(galaxy).unwrap();
+                    assert_eq!(universe.distance(galaxy, other), distance);
+                    assert!((0..universe.len()).all(|o| o == galaxy || universe.distance(galaxy, o) >= distance));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let expanded = "\
+....#........
+.........#...
+#............
+.............
+.............
+........#....
+.#...........
+............#
+.............
+.............
+.........#...
+#....#.......
+";
+        let universe = read_universe(TEST_INPUT_FILE, 2);
+        assert_eq!(universe.to_string(), expanded);
+        assert_eq!(universe.shape(), [12, 13]);
+        assert_eq!(read_universe(TEST_INPUT_FILE, 1).to_string(), read_input(TEST_INPUT_FILE).unwrap());
+        assert_eq!(read_universe(TEST_INPUT_FILE, 0).to_string(), "\
+..#....
+.....#.
+#......
+....#..
+.#.....
+......#
+.....#.
+#..#...
+");
+    }
+
+    #[test]
+    fn test_separate_axes() {
+        let input = parse_input(&read_input(TEST_INPUT_FILE).unwrap()).unwrap();
+        // Only the columns expand
+        let universe = Universe::with_expansion(&input, &Expansion::uniform(1i64), &Expansion::uniform(3));
+        assert_eq!(universe.shape(), [10, 16]);
+        assert_eq!(universe.to_string().lines().nth(3), Some("................"));
+        assert_eq!(universe.to_string().lines().next(), Some(".....#.........."));
+
+        // The sum is linear in both multipliers, independently
+        let sum = |rows: i64, cols: i64| Universe::with_expansion(&input, &Expansion::uniform(rows), &Expansion::uniform(cols)).sum_of_distances();
+        assert_eq!(sum(2, 2), 374);
+        assert_eq!(sum(10, 10), 1030);
+        assert_eq!(sum(2, 10) + sum(10, 2), sum(2, 2) + sum(10, 10));
+        assert_eq!(sum(1, 1) + sum(100, 100), sum(1, 100) + sum(100, 1));
+
+        // The first empty row (original row 3) becomes 5 rows, the other one (row 7) stays a single row
+        let rows = Expansion::uniform(1i64).with_gap(0, 5);
+        let universe = Universe::with_expansion(&input, &rows, &Expansion::uniform(1));
+        assert_eq!(universe.shape(), [14, 10]);
+        assert_eq!(universe.galaxies()[2], [2, 0]);
+        assert_eq!(universe.galaxies()[3], [8, 6]);
+        assert_eq!(universe.galaxies()[8], [13, 4]);
+        assert_eq!(universe.distance(0, 8), 14);
+    }
+
+    #[test]
+    fn test_coordinate_mapping() {
+        let input = parse_input(&read_input(TEST_INPUT_FILE).unwrap()).unwrap();
+        let rows = Expansion::uniform(BigInt::from(3)).with_gap(1, BigInt::from(0));
+        let cols = Expansion::uniform(BigInt::from(1_000_000_000_000i64));
+        let universe = Universe::with_expansion(&input, &rows, &cols);
+        for y in 0..input.height {
+            for x in 0..input.width {
+                let expanded = universe.to_expanded([y, x]);
+                if y != 7 {
+                    assert_eq!(universe.to_original(&expanded), Some([y, x]));
+                }
+            }
+        }
+        // Original row 7 has disappeared, the row after it in the expanded universe is original row 8
+        assert_eq!(universe.to_expanded([7, 0]), universe.to_expanded([8, 0]));
+        assert_eq!(universe.to_original(&universe.to_expanded([7, 0])), Some([8, 0]));
+
+        let [height, width] = universe.shape();
+        assert_eq!(height, BigInt::from(11));
+        let middle_of_gap = [BigInt::from(4), BigInt::from(2) + BigInt::from(500_000_000_000i64)];
+        assert_eq!(universe.to_original(&middle_of_gap), Some([3, 2]));
+        assert_eq!(universe.to_original(&[height.clone() - 1, width.clone() - 1]), Some([9, 9]));
+        assert_eq!(universe.to_original(&[height, BigInt::from(0)]), None);
+        assert_eq!(universe.to_original(&[BigInt::from(0), BigInt::from(-1)]), None);
+    }
+
+    #[test]
+    fn test_single_galaxy() {
+        let universe = Universe::new(&parse_input("..\n.#\n").unwrap(), Checked(5i64));
+        assert_eq!(universe.galaxies(), [[Checked(5), Checked(5)]]);
+        assert_eq!(universe.nearest(0), None);
+        assert_eq!(universe.farthest_pair(), None);
+        assert_eq!(universe.sum_of_distances(), Checked(0));
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{Automaton, Spring};
+
+
+/// All arrangements of a line of springs: the ways to fill in the unknown springs such that the damaged ones form the groups.
+///
+/// Arrangements are ranked in lexicographic order, with a working spring before a damaged one.
+/// For every position and state of the automaton, the number of ways to complete the line is precomputed,
+/// so that the arrangement of any rank can be found directly, without enumerating the ones before it.
+///
+/// # Example
+///
+/// ```
+/// use day12::parse_line;
+///
+/// let line = parse_line("?#?? 2").unwrap();
+/// let arrangements: Vec<String> = line.arrangements().map(|springs| springs.iter().map(|s| s.to_string()).collect()).collect();
+/// assert_eq!(arrangements, [".##.", "##.."]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Arrangements<'a> {
+    springs: &'a [Spring],
+    automaton: Automaton,
+    /// `completions[i][state]` is the number of ways to get from `state` to the accepting state with `springs[i..]`
+    completions: Vec<Vec<u128>>,
+    next_rank: u128,
+}
+
+impl<'a> Arrangements<'a> {
+    pub fn new(springs: &'a [Spring], group_lens: &[usize]) -> Arrangements<'a> {
+        let automaton = Automaton::new(group_lens);
+        let mut completions = vec![vec![0; automaton.n_states()]; springs.len() + 1];
+        completions[springs.len()][automaton.accepting()] = 1;
+        for (i, spring) in springs.iter().enumerate().rev() {
+            for state in 0..automaton.n_states() {
+                completions[i][state] = automaton.next(state, spring).map(|next| completions[i + 1][next]).sum();
+            }
+        }
+        Arrangements { springs, automaton, completions, next_rank: 0 }
+    }
+
+    /// The total number of arrangements, including the ones already iterated over.
+    pub fn total(&self) -> u128 {
+        self.completions[0][self.automaton.start()]
+    }
+
+    /// The arrangement with rank `k`, counting from 0, if there are more than `k` arrangements.
+    pub fn get(&self, mut k: u128) -> Option<Vec<Spring>> {
+        if k >= self.total() {
+            return None;
+        }
+        let mut state = self.automaton.start();
+        let mut arrangement = Vec::with_capacity(self.springs.len());
+        for (i, spring) in self.springs.iter().enumerate() {
+            let options = match spring {
+                Spring::UNKNOWN => [Spring::OK, Spring::DAMAGED].as_slice(),
+                known => std::slice::from_ref(known),
+            };
+            for option in options {
+                let Some(next) = self.automaton.next(state, option).next() else { continue };
+                let n_completions = self.completions[i + 1][next];
+                if k < n_completions {
+                    state = next;
+                    arrangement.push(option.clone());
+                    break;
+                }
+                k -= n_completions;
+            }
+        }
+        Some(arrangement)
+    }
+
+    /// An arrangement chosen uniformly at random, where `random_below(n)` returns a uniformly random number in `0..n`,
+    /// or `None` if there are no arrangements.
+    pub fn sample(&self, random_below: impl FnOnce(u128) -> u128) -> Option<Vec<Spring>> {
+        match self.total() {
+            0 => None,
+            count => self.get(random_below(count)),
+        }
+    }
+}
+
+impl Iterator for Arrangements<'_> {
+    type Item = Vec<Spring>;
+
+    fn next(&mut self) -> Option<Vec<Spring>> {
+        let arrangement = self.get(self.next_rank)?;
+        self.next_rank += 1;
+        Some(arrangement)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Vec<Spring>> {
+        self.next_rank = self.next_rank.saturating_add(n as u128).min(self.total());
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.total() - self.next_rank;
+        match usize::try_from(remaining) {
+            Ok(remaining) => (remaining, Some(remaining)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::{read_input, TEST_INPUT_FILE};
+    use crate::{parse_input, parse_line};
+    use super::*;
+
+    fn group_lens(arrangement: &[Spring]) -> Vec<usize> {
+        arrangement.split(|spring| *spring == Spring::OK).map(|group| group.len()).filter(|&len| len > 0).collect()
+    }
+
+    #[test]
+    fn test_enumerate() {
+        let input = parse_input(&read_input(TEST_INPUT_FILE).unwrap()).unwrap();
+        for line in input.lines.iter() {
+            let arrangements: Vec<Vec<Spring>> = line.arrangements().collect();
+            assert_eq!(arrangements.len() as u128, line.count_arrangements());
+            assert!(arrangements.windows(2).all(|pair| pair[0] < pair[1]));
+            for arrangement in &arrangements {
+                assert_eq!(group_lens(arrangement), line.group_lens.as_ref());
+                assert!(line.springs.iter().zip(arrangement).all(|(spring, filled)| *spring == Spring::UNKNOWN || spring == filled));
+            }
+        }
+    }
+
+    #[test]
+    fn test_unrank() {
+        let line = parse_line("?###???????? 3,2,1").unwrap().unfold(3);
+        let arrangements = line.arrangements();
+        assert_eq!(arrangements.total(), 2250);
+        let all: Vec<Vec<Spring>> = arrangements.clone().collect();
+        for k in [0, 1, 17, 1000, 2249] {
+            assert_eq!(arrangements.get(k).as_ref(), Some(&all[k as usize]));
+            assert_eq!(arrangements.clone().nth(k as usize).as_ref(), Some(&all[k as usize]));
+        }
+        assert_eq!(arrangements.get(2250), None);
+        assert_eq!(arrangements.clone().nth(5000), None);
+
+        let mut iter = line.arrangements();
+        iter.nth(2247);
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        assert_eq!(iter.count(), 2);
+    }
+
+    #[test]
+    fn test_sample() {
+        let line = parse_line("?#?? 2").unwrap();
+        let arrangements = line.arrangements();
+        assert_eq!(arrangements.sample(|_| 0), arrangements.get(0));
+        assert_eq!(arrangements.sample(|n| n - 1), arrangements.get(1));
+        assert_eq!(parse_line("#.# 1").unwrap().arrangements().sample(|_| unreachable!()), None);
+
+        // A huge line, far too many arrangements to enumerate
+        let line = parse_line("?###???????? 3,2,1").unwrap().unfold(20);
+        let arrangements = line.arrangements();
+        assert!(arrangements.total() > u64::MAX as u128);
+        let sample = arrangements.sample(|n| n / 3).unwrap();
+        assert_eq!(group_lens(&sample), line.group_lens.as_ref());
+    }
+}
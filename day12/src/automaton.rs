@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+
+use crate::Spring;
+
+
+/// The group lengths (the comma-separated numbers) as a deterministic finite automaton over working and damaged springs.
+///
+/// A sequence of springs matches the groups if it ends in the accepting state.
+/// The number of states is the total number of damaged springs in the groups, plus one for every gap between groups
+/// (and one at the start), since groups need to be separated by working springs.
+/// An unknown spring follows both transitions, so the automaton is run on all possible sequences at once,
+/// keeping track of the number of sequences in each state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Automaton {
+    /// The state after a working spring in every state, where the extra last state is a dead end for sequences
+    /// that do not match, so that runs can use dense tables without checking for missing transitions
+    next_ok: Vec<usize>,
+    /// The state after a damaged spring in every state, with the same dead state
+    next_damaged: Vec<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Transition {
+    next_ok: Option<usize>,
+    next_damaged: Option<usize>,
+}
+
+impl Automaton {
+    pub fn new(group_lens: &[usize]) -> Automaton {
+        assert!(group_lens.iter().all(|&group_len| group_len > 0), "Groups must not be empty");
+        // Any number of working springs before the first group
+        let mut states = vec![
+            Transition {
+                next_ok: Some(0),
+                next_damaged: group_lens.first().map(|_| 1),
+            }
+        ];
+        for (i, &group_len) in group_lens.iter().enumerate() {
+            // The remainder of the group, which must be damaged
+            for _ in 0..group_len - 1 {
+                states.push(Transition {
+                    next_ok: None,
+                    next_damaged: Some(states.len() + 1),
+                });
+            }
+            if i + 1 < group_lens.len() {
+                // The group is complete: at least one working spring, then any number of them before the next group
+                states.push(Transition {
+                    next_ok: Some(states.len() + 1),
+                    next_damaged: None,
+                });
+                states.push(Transition {
+                    next_ok: Some(states.len()),
+                    next_damaged: Some(states.len() + 1),
+                });
+            } else {
+                // The last group is complete: only working springs from here on
+                states.push(Transition {
+                    next_ok: Some(states.len()),
+                    next_damaged: None,
+                });
+            }
+        }
+        let dead = states.len();
+        Automaton {
+            next_ok: states.iter().map(|transition| transition.next_ok.unwrap_or(dead)).chain([dead]).collect(),
+            next_damaged: states.iter().map(|transition| transition.next_damaged.unwrap_or(dead)).chain([dead]).collect(),
+        }
+    }
+
+    /// The number of states, not counting the dead state.
+    pub fn n_states(&self) -> usize {
+        self.next_ok.len() - 1
+    }
+
+    pub fn start(&self) -> usize {
+        0
+    }
+
+    pub fn accepting(&self) -> usize {
+        self.n_states() - 1
+    }
+
+    fn dead(&self) -> usize {
+        self.n_states()
+    }
+
+    /// The states reached from `state` on `spring`: none, one, or for an unknown spring, possibly two.
+    pub fn next(&self, state: usize, spring: &Spring) -> impl Iterator<Item = usize> {
+        let (ok, damaged) = match spring {
+            Spring::OK => (Some(self.next_ok[state]), None),
+            Spring::DAMAGED => (None, Some(self.next_damaged[state])),
+            Spring::UNKNOWN => (Some(self.next_ok[state]), Some(self.next_damaged[state])),
+        };
+        let dead = self.dead();
+        ok.into_iter().chain(damaged).filter(move |&next| next != dead)
+    }
+
+    /// The number of sequences of springs matching `springs` in every state that they can reach.
+    pub fn run(&self, springs: &[Spring]) -> HashMap<usize, u128> {
+        let start = [(self.start(), 1)].into();
+        springs.iter().fold(start, |state_counts, spring| self.step(&state_counts, spring))
+    }
+
+    /// The number of sequences in every state after one more spring, given the number in every state before it.
+    pub fn step(&self, state_counts: &HashMap<usize, u128>, spring: &Spring) -> HashMap<usize, u128> {
+        let mut new_state_counts = HashMap::new();
+        for (&state, &count) in state_counts {
+            for next in self.next(state, spring) {
+                *new_state_counts.entry(next).or_insert(0) += count;
+            }
+        }
+        new_state_counts
+    }
+
+    /// The number of sequences of springs matching `springs` that are accepted.
+    ///
+    /// This is the same as looking up the accepting state after [`Automaton::run`], but keeps the counts
+    /// in two dense buffers instead, swapping them after every spring, which is about three times faster.
+    pub fn count_accepted(&self, springs: &[Spring]) -> u128 {
+        let mut state_counts = vec![0; self.next_ok.len()];
+        let mut new_state_counts = vec![0; self.next_ok.len()];
+        state_counts[self.start()] = 1;
+        for spring in springs {
+            new_state_counts.fill(0);
+            let counts = state_counts.iter().enumerate();
+            match spring {
+                Spring::OK => for (state, &count) in counts {
+                    new_state_counts[self.next_ok[state]] += count;
+                },
+                Spring::DAMAGED => for (state, &count) in counts {
+                    new_state_counts[self.next_damaged[state]] += count;
+                },
+                Spring::UNKNOWN => for (state, &count) in counts {
+                    new_state_counts[self.next_ok[state]] += count;
+                    new_state_counts[self.next_damaged[state]] += count;
+                },
+            }
+            // Forget the sequences that do not match, which would otherwise add up to overflow on long lines
+            new_state_counts[self.dead()] = 0;
+            std::mem::swap(&mut state_counts, &mut new_state_counts);
+        }
+        state_counts[self.accepting()]
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::{read_input, INPUT_FILE};
+    use crate::{parse_input, parse_line, PuzzleLine, REPETITIONS};
+    use super::*;
+
+    fn unfolded_input() -> Vec<PuzzleLine> {
+        let input = parse_input(&read_input(INPUT_FILE).unwrap()).unwrap();
+        input.lines.iter().map(|line| line.unfold(REPETITIONS)).collect()
+    }
+
+    fn count_sparse(automaton: &Automaton, springs: &[Spring]) -> u128 {
+        automaton.run(springs).get(&automaton.accepting()).copied().unwrap_or(0)
+    }
+
+    #[test]
+    fn test_states() {
+        // Start, the first group (complete), the gap, two damaged springs of the second group, the end
+        let automaton = Automaton::new(&[1, 3]);
+        assert_eq!(automaton.n_states(), 6);
+        assert_eq!(automaton.next(0, &Spring::UNKNOWN).collect::<Vec<_>>(), [0, 1]);
+        assert_eq!(automaton.next(1, &Spring::DAMAGED).count(), 0);
+        assert_eq!(automaton.next(5, &Spring::OK).collect::<Vec<_>>(), [5]);
+        assert_eq!(automaton.next(2, &Spring::DAMAGED).collect::<Vec<_>>(), [3]);
+
+        let line = parse_line("?###???????? 3,2,1").unwrap();
+        assert_eq!(Automaton::new(&line.group_lens).count_accepted(&line.springs), 10);
+    }
+
+    #[test]
+    fn test_no_groups() {
+        let automaton = Automaton::new(&[]);
+        assert_eq!(automaton.n_states(), 1);
+        assert_eq!(automaton.count_accepted(&[Spring::OK, Spring::UNKNOWN]), 1);
+        assert_eq!(automaton.count_accepted(&[Spring::OK, Spring::DAMAGED]), 0);
+    }
+
+    #[test]
+    fn test_dense_agrees_with_sparse() {
+        for line in unfolded_input() {
+            let automaton = Automaton::new(&line.group_lens);
+            assert_eq!(automaton.count_accepted(&line.springs), count_sparse(&automaton, &line.springs));
+        }
+        // Far more non-matching sequences than fit in a u128
+        let line = parse_line(&format!("{} 1", "?".repeat(300))).unwrap();
+        assert_eq!(Automaton::new(&line.group_lens).count_accepted(&line.springs), 300);
+    }
+
+    #[bench]
+    fn bench_count_sparse(b: &mut test::Bencher) {
+        let lines = unfolded_input();
+        b.iter(|| lines.iter().map(|line| count_sparse(&Automaton::new(&line.group_lens), &line.springs)).sum::<u128>());
+    }
+
+    #[bench]
+    fn bench_count_dense(b: &mut test::Bencher) {
+        let lines = unfolded_input();
+        b.iter(|| lines.iter().map(|line| Automaton::new(&line.group_lens).count_accepted(&line.springs)).sum::<u128>());
+    }
+}
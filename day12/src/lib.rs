@@ -1,33 +1,94 @@
-use std::fs;
-use std::io;
-use std::path::Path;
-use std::str::FromStr;
+#![cfg_attr(test, feature(test))]
+
+#[cfg(test)]
+extern crate test;
 
 use itertools::Itertools;
+use rayon::prelude::*;
 
-pub const INPUT_FILE: &str = "input.txt";
+use aoc::error::{parse_at, ParseError};
+use aoc::puzzle::Puzzle;
 
+pub mod arrangements;
+pub mod automaton;
+pub mod nonogram;
+pub mod part1;
+pub mod part2;
+pub mod solver;
 
-pub fn read_input(path: impl AsRef<Path>) -> io::Result<String> {
-    fs::read_to_string(path)
-}
+pub use arrangements::Arrangements;
+pub use automaton::Automaton;
+pub use part1::solve_part1;
+pub use part2::{solve_part2, REPETITIONS};
+pub use solver::LineSolver;
+
+
+pub struct Day12;
+
+impl Puzzle for Day12 {
+    type Input = PuzzleInput;
+    type Output1 = u128;
+    type Output2 = u128;
+    type ParseError = ParseError;
+
+    fn parse(input: &str) -> Result<PuzzleInput, Self::ParseError> {
+        parse_input(input)
+    }
 
-pub fn write_output(output: impl ToString) {
-    println!("{}", output.to_string());
+    fn part1(input: &PuzzleInput) -> u128 {
+        solve_part1(input)
+    }
+
+    fn part2(input: &PuzzleInput) -> u128 {
+        solve_part2(input)
+    }
 }
 
-pub fn parse_input(input: &str) -> PuzzleInput {
+
+pub fn parse_input(input: &str) -> Result<PuzzleInput, ParseError> {
     let puzzle_lines = input.lines().map(|line| {
-        let mut parts = line.split(' ');
-        let (part1, part2) = (parts.next().unwrap(), parts.next().unwrap());
-        let springs: Vec<_> = part1.chars().map(|c| Spring::from_char(&c)).collect();
-        let group_lens: Vec<_> = part2.split(',').map(|s| s.parse().unwrap()).collect();
-        PuzzleLine {
-            springs: springs.into_boxed_slice(),
-            group_lens: group_lens.into_boxed_slice(),
-        }
+        parse_line(line).map_err(|err| err.within(input, line))
     });
-    PuzzleInput { lines: puzzle_lines.collect_vec().into_boxed_slice() }
+    Ok(PuzzleInput { lines: puzzle_lines.collect::<Result<Vec<_>, _>>()?.into_boxed_slice() })
+}
+
+pub fn parse_line(line: &str) -> Result<PuzzleLine, ParseError> {
+    let (part1, part2) = line.split_once(' ')
+        .ok_or_else(|| ParseError::end_of(line, "` ` followed by group lengths"))?;
+    let springs: Vec<_> = part1.char_indices()
+        .map(|(i, c)| Spring::from_char(&c).ok_or_else(|| ParseError::at_char(line, i, "one of `.#?`")))
+        .collect::<Result<_, _>>()?;
+    let group_lens: Vec<_> = part2.split(',')
+        .map(|s| match parse_at(line, s, "a group length")? {
+            0 => Err(ParseError::at(line, s, "a positive group length")),
+            group_len => Ok(group_len),
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(PuzzleLine {
+        springs: springs.into_boxed_slice(),
+        group_lens: group_lens.into_boxed_slice(),
+    })
+}
+
+/// The number of ways in which the unknown springs can be filled in, such that the damaged springs form the given groups.
+///
+/// # Example
+///
+/// ```
+/// use day12::{count_arrangements, parse_line};
+///
+/// let line = parse_line("?###???????? 3,2,1").unwrap();
+/// assert_eq!(count_arrangements(&line.springs, &line.group_lens), 10);
+/// ```
+pub fn count_arrangements(springs: &[Spring], group_lens: &[usize]) -> u128 {
+    Automaton::new(group_lens).count_accepted(springs)
+}
+
+/// The sum of the numbers of arrangements of all lines, after unfolding each of them `repetitions` times.
+pub fn sum_arrangements(input: &PuzzleInput, repetitions: usize) -> u128 {
+    input.lines.par_iter()
+        .map(|line| line.unfold(repetitions).count_arrangements())
+        .sum()
 }
 
 #[derive(Clone, Debug)]
@@ -41,6 +102,24 @@ pub struct PuzzleLine {
     pub group_lens: Box<[usize]>,
 }
 
+impl PuzzleLine {
+    /// The line repeated `repetitions` times, with the copies of the springs separated by an unknown spring.
+    pub fn unfold(&self, repetitions: usize) -> PuzzleLine {
+        let springs = vec![self.springs.as_ref(); repetitions].join(&Spring::UNKNOWN).into();
+        let group_lens = self.group_lens.repeat(repetitions).into();
+        PuzzleLine { springs, group_lens }
+    }
+
+    pub fn count_arrangements(&self) -> u128 {
+        count_arrangements(&self.springs, &self.group_lens)
+    }
+
+    /// The arrangements of the line, in lexicographic order with working springs first.
+    pub fn arrangements(&self) -> Arrangements<'_> {
+        Arrangements::new(&self.springs, &self.group_lens)
+    }
+}
+
 impl std::fmt::Display for PuzzleLine {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let springs = self.springs.iter().map(|s| s.to_string()).collect::<String>();
@@ -49,18 +128,18 @@ impl std::fmt::Display for PuzzleLine {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Spring {
     OK, DAMAGED, UNKNOWN
 }
 
 impl Spring {
-    pub fn from_char(c: &char) -> Spring {
+    pub fn from_char(c: &char) -> Option<Spring> {
         match c {
-            '.' => Spring::OK,
-            '#' => Spring::DAMAGED,
-            '?' => Spring::UNKNOWN,
-            _ => panic!("Invalid spring character: {}", c),
+            '.' => Some(Spring::OK),
+            '#' => Some(Spring::DAMAGED),
+            '?' => Some(Spring::UNKNOWN),
+            _ => None,
         }
     }
 }
@@ -77,26 +156,77 @@ impl std::fmt::Display for Spring {
 }
 
 
-/// Parse a whitespace-separated list of numbers
-/// 
-/// # Arguments
-/// 
-/// * `numbers` - A string containing a whitespace-separated list of numbers
-/// 
-/// # Returns
-/// 
-/// A vector of parsed numbers
-/// 
-/// # Example
-/// 
-/// ```
-/// let numbers = "1 2 3 4 5";
-/// let parsed = day11::parse_numbers(numbers);
-/// assert_eq!(parsed, Ok(vec![1, 2, 3, 4, 5]));
-/// ```
-pub fn parse_numbers<T: FromStr>(numbers: &str) -> Result<Vec<T>, <T as FromStr>::Err> {
-    numbers
-        .split_whitespace()
-        .map(|x| x.parse())
-        .collect()
+#[cfg(test)]
+mod tests {
+    use aoc::aux::rng::Rng;
+    use super::*;
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("???.### 1,1,3\n.??..??...?##. 1,1,3\n?#?#?#?#?#?#?#? 1,3,x\n").unwrap_err();
+        assert_eq!(err, ParseError::new(3, 21, "x", "a group length"));
+
+        let err = parse_input("???.### 1,1,3\n.??..?!...?##. 1,1,3\n").unwrap_err();
+        assert_eq!(err, ParseError::new(2, 7, "!", "one of `.#?`"));
+
+        let err = parse_input("???.### 1,0,3\n").unwrap_err();
+        assert_eq!(err, ParseError::new(1, 11, "0", "a positive group length"));
+    }
+
+    /// The number of arrangements by trying both options for every unknown spring, skipping whole groups at once.
+    fn count_recursive(springs: &[Spring], group_lens: &[usize]) -> u128 {
+        let Some((&group_len, other_groups)) = group_lens.split_first() else {
+            return if springs.contains(&Spring::DAMAGED) { 0 } else { 1 };
+        };
+        let min_space = group_lens.iter().sum::<usize>() + group_lens.len() - 1;
+        if springs.len() < min_space {
+            return 0;
+        }
+        let skip_working = |springs: &[Spring]| count_recursive(&springs[1..], group_lens);
+        let match_group = |springs: &[Spring]| {
+            // The group must fit before the next working spring, and must not be followed by a damaged one
+            if springs[..group_len].contains(&Spring::OK) || springs.get(group_len) == Some(&Spring::DAMAGED) {
+                return 0;
+            }
+            count_recursive(&springs[(group_len + 1).min(springs.len())..], other_groups)
+        };
+        match springs[0] {
+            Spring::OK => skip_working(springs),
+            Spring::DAMAGED => match_group(springs),
+            Spring::UNKNOWN => skip_working(springs) + match_group(springs),
+        }
+    }
+
+    #[test]
+    fn test_against_recursive() {
+        let mut rng = Rng(0x9E3779B97F4A7C15);
+        for _ in 0..2000 {
+            let springs: Vec<Spring> = (0..rng.below(16))
+                .map(|_| [Spring::OK, Spring::DAMAGED, Spring::UNKNOWN, Spring::UNKNOWN][rng.below(4)].clone())
+                .collect();
+            let group_lens: Vec<usize> = (0..rng.below(5)).map(|_| 1 + rng.below(4)).collect();
+            assert_eq!(count_arrangements(&springs, &group_lens), count_recursive(&springs, &group_lens));
+        }
+    }
+
+    #[test]
+    fn test_unfold() {
+        let line = parse_line(".# 1").unwrap();
+        assert_eq!(line.unfold(5), parse_line(".#?.#?.#?.#?.# 1,1,1,1,1").unwrap());
+        assert_eq!(line.unfold(1), line);
+
+        let line = parse_line("???.### 1,1,3").unwrap();
+        assert_eq!(line.count_arrangements(), 1);
+        assert_eq!(line.unfold(REPETITIONS).count_arrangements(), 1);
+        let line = parse_line("?###???????? 3,2,1").unwrap();
+        assert_eq!(line.unfold(REPETITIONS).count_arrangements(), 506250);
+    }
+
+    #[test]
+    fn test_long_line() {
+        // 30 groups of 1 in 120 unknown springs: choose the 30 positions among 91, since 29 separators are fixed
+        let springs = vec![Spring::UNKNOWN; 120];
+        assert_eq!(count_arrangements(&springs, &[1; 30]), 1004181978362275337408784);
+        assert_eq!(count_arrangements(&springs, &[]), 1);
+    }
 }
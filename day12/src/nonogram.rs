@@ -0,0 +1,284 @@
+use std::fmt;
+use std::str::FromStr;
+
+use aoc::aux::Grid;
+use aoc::error::{parse_at, ParseError};
+
+use crate::{LineSolver, Spring};
+
+
+// A nonogram has group lengths for every row and every column of a grid, so each of them is a line as in the puzzle.
+// Lines are solved one at a time with the `LineSolver`, filling in the springs that are the same in all arrangements
+// of that line, until nothing changes; each filled-in spring may allow more deductions in the crossing line.
+// This solves most puzzles made for humans. Otherwise, the solver guesses the first unknown spring and backtracks,
+// until it has found two solutions or ruled out all guesses.
+
+
+/// The group lengths of every row and every column.
+///
+/// The text format has a block of rows and a block of columns, each with one line of group lengths per row or column,
+/// where `0` stands for a line without any groups.
+///
+/// # Example
+///
+/// ```
+/// use day12::nonogram::{Nonogram, Solution};
+///
+/// let nonogram: Nonogram = "rows:\n1\n3\n1,1\n\ncolumns:\n2\n2\n2\n".parse().unwrap();
+/// let Solution::Unique(grid) = nonogram.solve() else { panic!() };
+/// assert_eq!(grid.to_string(), ".#.\n###\n#.#\n");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Nonogram {
+    pub rows: Vec<Vec<usize>>,
+    pub columns: Vec<Vec<usize>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Solution {
+    Unique(Grid<Spring>),
+    /// Two of the solutions
+    Multiple(Grid<Spring>, Grid<Spring>),
+    Unsolvable,
+}
+
+impl Nonogram {
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn width(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// The group lengths of a grid without unknown springs, that is, the nonogram it solves.
+    pub fn from_grid(grid: &Grid<Spring>) -> Nonogram {
+        let rows = grid.rows().map(|row| group_lens(row.iter())).collect();
+        let columns = grid.columns().map(group_lens).collect();
+        Nonogram { rows, columns }
+    }
+
+    /// The grid with everything that follows from solving lines one at a time, or `None` if there is a contradiction.
+    pub fn deduce(&self) -> Option<Grid<Spring>> {
+        let mut grid = self.unknown_grid();
+        self.deduce_from(&mut grid).then_some(grid)
+    }
+
+    pub fn solve(&self) -> Solution {
+        let mut solutions = vec![];
+        self.search(self.unknown_grid(), &mut solutions);
+        let mut solutions = solutions.into_iter();
+        match (solutions.next(), solutions.next()) {
+            (None, _) => Solution::Unsolvable,
+            (Some(solution), None) => Solution::Unique(solution),
+            (Some(first), Some(second)) => Solution::Multiple(first, second),
+        }
+    }
+
+    fn unknown_grid(&self) -> Grid<Spring> {
+        Grid::new(self.height(), self.width(), vec![Spring::UNKNOWN; self.height() * self.width()])
+    }
+
+    /// Add solutions that extend `grid` to `solutions`, until there are two of them.
+    fn search(&self, mut grid: Grid<Spring>, solutions: &mut Vec<Grid<Spring>>) {
+        if solutions.len() >= 2 || !self.deduce_from(&mut grid) {
+            return;
+        }
+        let Some(unknown) = grid.position(|spring| *spring == Spring::UNKNOWN) else {
+            solutions.push(grid);
+            return;
+        };
+        for guess in [Spring::OK, Spring::DAMAGED] {
+            let mut guessed = grid.clone();
+            guessed[unknown] = guess;
+            self.search(guessed, solutions);
+        }
+    }
+
+    /// Fill in `grid` by solving lines until nothing changes, returning false if some line has no arrangements.
+    fn deduce_from(&self, grid: &mut Grid<Spring>) -> bool {
+        let mut dirty_rows = vec![true; self.height()];
+        let mut dirty_columns = vec![true; self.width()];
+        while dirty_rows.contains(&true) || dirty_columns.contains(&true) {
+            for y in take_dirty(&mut dirty_rows) {
+                let Some(forced) = forced(&self.rows[y], grid.row(y).iter()) else { return false };
+                for (x, spring) in forced.into_iter().enumerate() {
+                    if grid[(y, x)] != spring {
+                        grid[(y, x)] = spring;
+                        dirty_columns[x] = true;
+                    }
+                }
+            }
+            for x in take_dirty(&mut dirty_columns) {
+                let Some(forced) = forced(&self.columns[x], grid.column(x)) else { return false };
+                for (y, spring) in forced.into_iter().enumerate() {
+                    if grid[(y, x)] != spring {
+                        grid[(y, x)] = spring;
+                        dirty_rows[y] = true;
+                    }
+                }
+            }
+        }
+        true
+    }
+}
+
+/// The indices of the lines that are marked as dirty, clearing the marks.
+fn take_dirty(dirty: &mut [bool]) -> Vec<usize> {
+    (0..dirty.len()).filter(|&i| std::mem::take(&mut dirty[i])).collect()
+}
+
+/// The springs of a line that are the same in all of its arrangements, or `None` if there are no arrangements.
+fn forced<'a>(group_lens: &[usize], line: impl Iterator<Item = &'a Spring>) -> Option<Vec<Spring>> {
+    let mut solver = LineSolver::new(group_lens);
+    for spring in line {
+        solver.push(spring.clone());
+    }
+    solver.forced(0)
+}
+
+fn group_lens<'a>(line: impl Iterator<Item = &'a Spring>) -> Vec<usize> {
+    let mut group_lens = vec![];
+    let mut group_len = 0;
+    for spring in line.chain([&Spring::OK]) {
+        match spring {
+            Spring::DAMAGED => group_len += 1,
+            _ if group_len > 0 => group_lens.push(std::mem::take(&mut group_len)),
+            _ => (),
+        }
+    }
+    group_lens
+}
+
+impl FromStr for Nonogram {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Nonogram, ParseError> {
+        let mut blocks = input.split("\n\n").filter(|block| !block.trim().is_empty());
+        let mut parse_block = |header: &str| {
+            let block = blocks.next().ok_or_else(|| ParseError::end_of(input, format!("`{}` and group lengths", header)))?;
+            parse_clues(block, header).map_err(|err| err.within(input, block))
+        };
+        let rows = parse_block("rows:")?;
+        let columns = parse_block("columns:")?;
+        if let Some(block) = blocks.next() {
+            return Err(ParseError::at(input, block, "the end of the input"));
+        }
+        Ok(Nonogram { rows, columns })
+    }
+}
+
+fn parse_clues(block: &str, header: &str) -> Result<Vec<Vec<usize>>, ParseError> {
+    let mut lines = block.lines();
+    let first = lines.next().unwrap_or_default();
+    if first != header {
+        return Err(ParseError::at(block, first, format!("`{}`", header)));
+    }
+    lines
+        .map(|line| match line {
+            "0" => Ok(vec![]),
+            _ => line.split(',')
+                .map(|s| match parse_at(line, s, "a group length")? {
+                    0 => Err(ParseError::at(line, s, "a positive group length")),
+                    group_len => Ok(group_len),
+                })
+                .collect::<Result<_, _>>()
+                .map_err(|err| err.within(block, line)),
+        })
+        .collect()
+}
+
+impl fmt::Display for Nonogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let write_block = |f: &mut fmt::Formatter<'_>, header: &str, lines: &[Vec<usize>]| -> fmt::Result {
+            writeln!(f, "{}", header)?;
+            for group_lens in lines {
+                match group_lens.is_empty() {
+                    true => writeln!(f, "0")?,
+                    false => writeln!(f, "{}", group_lens.iter().map(|len| len.to_string()).collect::<Vec<_>>().join(","))?,
+                }
+            }
+            Ok(())
+        };
+        write_block(f, "rows:", &self.rows)?;
+        writeln!(f)?;
+        write_block(f, "columns:", &self.columns)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::rng::Rng;
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let input = "rows:\n1\n0\n2,1\n\ncolumns:\n1\n1\n0\n1\n";
+        let nonogram: Nonogram = input.parse().unwrap();
+        assert_eq!(nonogram.rows, [vec![1], vec![], vec![2, 1]]);
+        assert_eq!(nonogram.columns, [vec![1], vec![1], vec![], vec![1]]);
+        assert_eq!(nonogram.to_string(), input);
+
+        assert_eq!("rows:\n1\n\ncols:\n1\n".parse::<Nonogram>(), Err(ParseError::new(4, 1, "cols:", "`columns:`")));
+        assert_eq!("rows:\n1\n2,0\n\ncolumns:\n1\n".parse::<Nonogram>(), Err(ParseError::new(3, 3, "0", "a positive group length")));
+        assert_eq!("rows:\n1\n".parse::<Nonogram>(), Err(ParseError::new(3, 1, "", "`columns:` and group lengths")));
+    }
+
+    #[test]
+    fn test_deduce() {
+        // Solvable line by line
+        let grid = Grid::parse(".###.\n##.##\n#####\n.###.\n..#..", "one of `.#?`", |c| Spring::from_char(&c)).unwrap();
+        let nonogram = Nonogram::from_grid(&grid);
+        assert_eq!(nonogram.deduce(), Some(grid.clone()));
+        assert_eq!(nonogram.solve(), Solution::Unique(grid));
+
+        // Nothing to deduce: either diagonal
+        let diagonal = Grid::parse("#.\n.#", "one of `.#?`", |c| Spring::from_char(&c)).unwrap();
+        let antidiagonal = Grid::parse(".#\n#.", "one of `.#?`", |c| Spring::from_char(&c)).unwrap();
+        let nonogram = Nonogram::from_grid(&diagonal);
+        assert_eq!(nonogram.deduce(), Some(Grid::parse("??\n??", "one of `.#?`", |c| Spring::from_char(&c)).unwrap()));
+        assert_eq!(nonogram.solve(), Solution::Multiple(antidiagonal, diagonal));
+
+        let nonogram = Nonogram { rows: vec![vec![2], vec![]], columns: vec![vec![], vec![]] };
+        assert_eq!(nonogram.deduce(), None);
+        assert_eq!(nonogram.solve(), Solution::Unsolvable);
+    }
+
+    #[test]
+    fn test_backtracking() {
+        // Unique, but every line has several arrangements on its own
+        let grid = Grid::parse("#..#\n.##.\n....\n....", "one of `.#?`", |c| Spring::from_char(&c)).unwrap();
+        let nonogram = Nonogram::from_grid(&grid);
+        assert_eq!(nonogram.deduce(), Some(Grid::parse("????\n????\n....\n....", "one of `.#?`", |c| Spring::from_char(&c)).unwrap()));
+        assert_eq!(nonogram.solve(), Solution::Unique(grid));
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let (height, width) = (3, 4);
+        let grids: Vec<Grid<Spring>> = (0..1u32 << (height * width))
+            .map(|bits| Grid::from_fn(height, width, |y, x| match bits >> (y * width + x) & 1 {
+                0 => Spring::OK,
+                _ => Spring::DAMAGED,
+            }))
+            .collect();
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..100 {
+            let grid = &grids[rng.below(grids.len())];
+            let nonogram = Nonogram::from_grid(grid);
+            let n_solutions = grids.iter().filter(|other| Nonogram::from_grid(other) == nonogram).count();
+            match nonogram.solve() {
+                Solution::Unique(solution) => assert_eq!((n_solutions, &solution), (1, grid)),
+                Solution::Multiple(first, second) => {
+                    assert!(n_solutions > 1);
+                    assert_ne!(first, second);
+                    assert_eq!(Nonogram::from_grid(&first), nonogram);
+                    assert_eq!(Nonogram::from_grid(&second), nonogram);
+                }
+                Solution::Unsolvable => panic!("{} has a solution", nonogram),
+            }
+        }
+    }
+}
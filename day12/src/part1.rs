@@ -0,0 +1,38 @@
+use crate::{sum_arrangements, PuzzleInput};
+
+
+pub fn solve_part1(input: &PuzzleInput) -> u128 {
+    sum_arrangements(input, 1)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::{read_input, INPUT_FILE};
+    use aoc::puzzle::read_and_solve_part1;
+    use crate::{parse_input, Day12};
+
+    const TEST_SOLUTION_L1: u128 = 1;
+    const TEST_SOLUTION_L2: u128 = 4;
+    const TEST_SOLUTION_L6: u128 = 10;
+    const TEST_SOLUTION: u128 = 21;
+
+    #[test]
+    fn test_part1() {
+        let test_input = parse_input(&read_input("test_input.txt").unwrap()).unwrap();
+        let output_1 = test_input.lines[0].count_arrangements();
+        let output_2 = test_input.lines[1].count_arrangements();
+        let output_6 = test_input.lines[5].count_arrangements();
+        assert_eq!(output_1, TEST_SOLUTION_L1);
+        assert_eq!(output_2, TEST_SOLUTION_L2);
+        assert_eq!(output_6, TEST_SOLUTION_L6);
+
+        let test_output = read_and_solve_part1::<Day12>("test_input.txt");
+        assert_eq!(test_output, TEST_SOLUTION);
+    }
+
+    #[bench]
+    fn bench_part1(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part1::<Day12>(INPUT_FILE));
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{sum_arrangements, PuzzleInput};
+
+
+pub const REPETITIONS: usize = 5;
+
+
+pub fn solve_part2(input: &PuzzleInput) -> u128 {
+    sum_arrangements(input, REPETITIONS)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::{read_input, INPUT_FILE};
+    use aoc::puzzle::read_and_solve_part2;
+    use crate::{parse_input, Day12};
+    use super::*;
+
+    const TEST_SOLUTION_L1: u128 = 1;
+    const TEST_SOLUTION_L2: u128 = 16384;
+    const TEST_SOLUTION_L6: u128 = 506250;
+    const TEST_SOLUTION: u128 = 525152;
+
+    #[test]
+    fn test_part2() {
+        let test_input = parse_input(&read_input("test_input.txt").unwrap()).unwrap();
+        let output_1 = test_input.lines[0].unfold(REPETITIONS).count_arrangements();
+        let output_2 = test_input.lines[1].unfold(REPETITIONS).count_arrangements();
+        let output_6 = test_input.lines[5].unfold(REPETITIONS).count_arrangements();
+        assert_eq!(output_1, TEST_SOLUTION_L1);
+        assert_eq!(output_2, TEST_SOLUTION_L2);
+        assert_eq!(output_6, TEST_SOLUTION_L6);
+
+        let test_output = read_and_solve_part2::<Day12>("test_input.txt");
+        assert_eq!(test_output, TEST_SOLUTION);
+    }
+
+    #[bench]
+    fn bench_part2(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part2::<Day12>(INPUT_FILE));
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+
+use crate::{Automaton, Spring};
+
+
+/// A solver for a line that is fed one spring at a time, keeping the number of sequences in every state of the automaton.
+///
+/// At any point it can count the arrangements of the line so far, assuming that any number of unknown springs follow,
+/// and deduce which springs are the same in every arrangement, as when solving a nonogram line by line.
+///
+/// # Example
+///
+/// ```
+/// use day12::{LineSolver, Spring};
+///
+/// let mut solver = LineSolver::new(&[1, 1, 3]);
+/// for c in "???.###".chars() {
+///     solver.push(Spring::from_char(&c).unwrap());
+/// }
+/// assert_eq!(solver.count(), 1);
+/// let forced: String = solver.forced(0).unwrap().iter().map(|s| s.to_string()).collect();
+/// assert_eq!(forced, "#.#.###");
+///
+/// // With three more unknown springs after "?#", the group can start at either spring
+/// let mut solver = LineSolver::new(&[3]);
+/// solver.push(Spring::UNKNOWN);
+/// solver.push(Spring::DAMAGED);
+/// assert_eq!(solver.completions(3), 2);
+/// assert_eq!(solver.forced(3), Some(vec![Spring::UNKNOWN, Spring::DAMAGED]));
+/// assert_eq!(solver.forced(0), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct LineSolver {
+    automaton: Automaton,
+    springs: Vec<Spring>,
+    /// `state_counts[i]` is the number of sequences in every state after the first `i` springs
+    state_counts: Vec<HashMap<usize, u128>>,
+}
+
+impl LineSolver {
+    pub fn new(group_lens: &[usize]) -> LineSolver {
+        let automaton = Automaton::new(group_lens);
+        let state_counts = vec![[(automaton.start(), 1)].into()];
+        LineSolver { automaton, springs: vec![], state_counts }
+    }
+
+    pub fn push(&mut self, spring: Spring) {
+        let state_counts = self.automaton.step(self.state_counts(), &spring);
+        self.springs.push(spring);
+        self.state_counts.push(state_counts);
+    }
+
+    /// The springs fed so far.
+    pub fn springs(&self) -> &[Spring] {
+        &self.springs
+    }
+
+    pub fn len(&self) -> usize {
+        self.springs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.springs.is_empty()
+    }
+
+    /// The number of sequences in every state that the springs so far can reach.
+    pub fn state_counts(&self) -> &HashMap<usize, u128> {
+        self.state_counts.last().unwrap()
+    }
+
+    /// The number of arrangements if the line ends here.
+    pub fn count(&self) -> u128 {
+        self.completions(0)
+    }
+
+    /// The number of arrangements if the line continues with `remaining` unknown springs.
+    pub fn completions(&self, remaining: usize) -> u128 {
+        let tail = self.tail_completions(remaining);
+        self.state_counts().iter().map(|(&state, &count)| count * tail[state]).sum()
+    }
+
+    /// The springs so far, where every unknown spring that is the same in all arrangements is replaced by that spring,
+    /// if the line continues with `remaining` unknown springs;
+    /// or `None` if there are no arrangements at all.
+    pub fn forced(&self, remaining: usize) -> Option<Vec<Spring>> {
+        let mut completions = self.tail_completions(remaining);
+        let mut forced = self.springs.clone();
+        for (i, spring) in self.springs.iter().enumerate().rev() {
+            // The number of arrangements through each option for this spring
+            let through = |option: &Spring| -> u128 {
+                if *spring != Spring::UNKNOWN && spring != option {
+                    return 0;
+                }
+                self.state_counts[i].iter()
+                    .map(|(&state, &count)| count * self.automaton.next(state, option).map(|next| completions[next]).sum::<u128>())
+                    .sum()
+            };
+            match (through(&Spring::OK), through(&Spring::DAMAGED)) {
+                (0, 0) => return None,
+                (_, 0) => forced[i] = Spring::OK,
+                (0, _) => forced[i] = Spring::DAMAGED,
+                _ => (),
+            }
+            completions = (0..self.automaton.n_states())
+                .map(|state| self.automaton.next(state, spring).map(|next| completions[next]).sum())
+                .collect();
+        }
+        (self.completions(remaining) > 0).then_some(forced)
+    }
+
+    /// The number of ways to get from every state to the accepting state with `remaining` unknown springs.
+    fn tail_completions(&self, remaining: usize) -> Vec<u128> {
+        let mut completions = vec![0; self.automaton.n_states()];
+        completions[self.automaton.accepting()] = 1;
+        for _ in 0..remaining {
+            completions = (0..self.automaton.n_states())
+                .map(|state| self.automaton.next(state, &Spring::UNKNOWN).map(|next| completions[next]).sum())
+                .collect();
+        }
+        completions
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::{read_input, TEST_INPUT_FILE};
+    use crate::{count_arrangements, parse_input, parse_line, Arrangements};
+    use super::*;
+
+    #[test]
+    fn test_against_arrangements() {
+        let input = parse_input(&read_input(TEST_INPUT_FILE).unwrap()).unwrap();
+        for line in input.lines.iter() {
+            let mut solver = LineSolver::new(&line.group_lens);
+            for (fed, spring) in line.springs.iter().enumerate() {
+                solver.push(spring.clone());
+                let remaining = line.springs.len() - fed - 1;
+                let mut springs = line.springs[..=fed].to_vec();
+                springs.extend(vec![Spring::UNKNOWN; remaining]);
+                assert_eq!(solver.completions(remaining), count_arrangements(&springs, &line.group_lens));
+
+                let arrangements: Vec<Vec<Spring>> = Arrangements::new(&springs, &line.group_lens).collect();
+                let expected: Vec<Spring> = (0..=fed)
+                    .map(|i| match &arrangements[0][i] {
+                        first if arrangements.iter().all(|arrangement| arrangement[i] == *first) => first.clone(),
+                        _ => Spring::UNKNOWN,
+                    })
+                    .collect();
+                assert_eq!(solver.forced(remaining), Some(expected));
+            }
+            assert_eq!(solver.count(), line.count_arrangements());
+        }
+    }
+
+    #[test]
+    fn test_no_arrangements() {
+        let line = parse_line("#.#.? 3").unwrap();
+        let mut solver = LineSolver::new(&line.group_lens);
+        assert_eq!(solver.completions(5), 3);
+        assert!(solver.is_empty());
+        assert_eq!(solver.forced(0), None);
+        for spring in line.springs.iter() {
+            solver.push(spring.clone());
+        }
+        assert_eq!(solver.len(), 5);
+        assert!(solver.state_counts().is_empty());
+        assert_eq!(solver.completions(100), 0);
+        assert_eq!(solver.forced(100), None);
+    }
+}
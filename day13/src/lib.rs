@@ -1,19 +1,45 @@
-use std::fs;
-use std::io;
-use std::path::Path;
+#![cfg_attr(test, feature(test))]
+
+#[cfg(test)]
+extern crate test;
+
 use std::str::FromStr;
 
-pub const INPUT_FILE: &str = "input.txt";
+use aoc::aux::Grid;
+use aoc::error::ParseError;
+use aoc::puzzle::Puzzle;
 
+pub mod part1;
+pub mod part2;
+pub mod reflection;
 
-pub fn read_input(path: impl AsRef<Path>) -> io::Result<String> {
-    fs::read_to_string(path)
-}
+pub use part1::solve_part1;
+pub use part2::solve_part2;
+pub use reflection::{find_reflections, Axis, Reflection};
+
+
+pub struct Day13;
+
+impl Puzzle for Day13 {
+    type Input = PuzzleInput;
+    type Output1 = u64;
+    type Output2 = u64;
+    type ParseError = <PuzzleInput as FromStr>::Err;
 
-pub fn write_output(output: impl ToString) {
-    println!("{}", output.to_string());
+    fn parse(input: &str) -> Result<PuzzleInput, Self::ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &PuzzleInput) -> u64 {
+        solve_part1(input)
+    }
+
+    fn part2(input: &PuzzleInput) -> u64 {
+        solve_part2(input)
+    }
 }
 
+
 pub fn parse_input(input: &str) -> Result<PuzzleInput, <PuzzleInput as FromStr>::Err> {
     input.parse()
 }
@@ -24,43 +50,20 @@ pub struct PuzzleInput {
 }
 
 impl FromStr for PuzzleInput {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<PuzzleInput, Self::Err> {
         let problem_strings = s.split("\n\n");
-        let lines: Result<Vec<_>, _> = problem_strings.map(PuzzleLine::from_str).collect();
+        let lines: Result<Vec<_>, _> = problem_strings
+            .map(|block| Grid::parse(block, "`.` or `#`", Entry::from_char).map_err(|err| err.within(s, block)))
+            .collect();
         Ok(PuzzleInput {
             lines: lines?.into_boxed_slice()
         })
     }
 }
 
-#[derive(Clone, Debug)]
-pub struct PuzzleLine {
-    pub height: usize,
-    pub width: usize,
-    pub entries: Box<[Entry]>
-}
-
-impl FromStr for PuzzleLine {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<PuzzleLine, Self::Err> {
-        let lines: Vec<_> = s.lines().collect();
-        let height = lines.len();
-        let width = lines[0].len();
-        let mut entries: Vec<Entry> = Vec::with_capacity(height * width);
-        for line in lines {
-            let row_entries: Result<Vec<_>, _> = line.chars().map(Entry::from_char).collect();
-            entries.extend(row_entries?);
-        }
-        Ok(PuzzleLine {
-            height,
-            width,
-            entries: entries.into_boxed_slice()
-        })
-    }
-}
+pub type PuzzleLine = Grid<Entry>;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Entry {
@@ -68,11 +71,11 @@ pub enum Entry {
 }
 
 impl Entry {
-    pub fn from_char(c: char) -> Result<Entry, String> {
+    pub fn from_char(c: char) -> Option<Entry> {
         match c {
-            '.' => Ok(Entry::ASH),
-            '#' => Ok(Entry::ROCK),
-            _ => Err(format!("Invalid character: {}", c))
+            '.' => Some(Entry::ASH),
+            '#' => Some(Entry::ROCK),
+            _ => None
         }
     }
 }
@@ -87,26 +90,13 @@ impl std::fmt::Display for Entry {
 }
 
 
-/// Parse a whitespace-separated list of numbers
-/// 
-/// # Arguments
-/// 
-/// * `numbers` - A string containing a whitespace-separated list of numbers
-/// 
-/// # Returns
-/// 
-/// A vector of parsed numbers
-/// 
-/// # Example
-/// 
-/// ```
-/// let numbers = "1 2 3 4 5";
-/// let parsed = day13::parse_numbers(numbers);
-/// assert_eq!(parsed, Ok(vec![1, 2, 3, 4, 5]));
-/// ```
-pub fn parse_numbers<T: FromStr>(numbers: &str) -> Result<Vec<T>, <T as FromStr>::Err> {
-    numbers
-        .split_whitespace()
-        .map(|x| x.parse())
-        .collect()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("#.#\n.#.\n\n##.\n#.o\n").unwrap_err();
+        assert_eq!(err, ParseError::new(5, 3, "o", "`.` or `#`"));
+    }
 }
@@ -0,0 +1,43 @@
+use crate::{find_reflections, PuzzleInput, PuzzleLine};
+
+
+pub fn solve_part1(input: &PuzzleInput) -> u64 {
+    input.lines.iter().map(solve_line).sum()
+}
+
+fn solve_line(line: &PuzzleLine) -> u64 {
+    find_reflections(line, 0).iter()
+        .find(|reflection| reflection.mismatches() == 0)
+        .expect("a perfect reflection")
+        .summary()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::{read_input, INPUT_FILE};
+    use aoc::puzzle::read_and_solve_part1;
+    use crate::{parse_input, Day13};
+    use super::*;
+
+    const TEST_SOLUTION_1: u64 = 5;
+    const TEST_SOLUTION_2: u64 = 400;
+    const TEST_SOLUTION: u64 = 405;
+
+    #[test]
+    fn test_part1() {
+        let test_input = parse_input(&read_input("test_input.txt").unwrap()).unwrap();
+        let output_1 = solve_line(&test_input.lines[0]);
+        let output_2 = solve_line(&test_input.lines[1]);
+        assert_eq!(output_1, TEST_SOLUTION_1);
+        assert_eq!(output_2, TEST_SOLUTION_2);
+
+        let test_output = read_and_solve_part1::<Day13>("test_input.txt");
+        assert_eq!(test_output, TEST_SOLUTION);
+    }
+
+    #[bench]
+    fn bench_part1(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part1::<Day13>(INPUT_FILE));
+    }
+}
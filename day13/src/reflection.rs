@@ -0,0 +1,137 @@
+use aoc::aux::Grid;
+
+
+/// Whether a line of reflection runs between two rows or between two columns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+/// A line of reflection of a pattern, with the pairs of mirrored cells that do not match.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Reflection {
+    pub axis: Axis,
+    /// The number of rows above a horizontal line, or the number of columns left of a vertical one
+    pub position: usize,
+    /// The `(y, x)` indices of the mismatched pairs, top or left one first; flipping either cell removes the mismatch
+    pub smudges: Vec<[(usize, usize); 2]>,
+}
+
+impl Reflection {
+    pub fn mismatches(&self) -> usize {
+        self.smudges.len()
+    }
+
+    /// The number summarizing the line in the puzzle: 100 times the rows above it, or the columns left of it.
+    pub fn summary(&self) -> u64 {
+        match self.axis {
+            Axis::Horizontal => 100 * self.position as u64,
+            Axis::Vertical => self.position as u64,
+        }
+    }
+}
+
+/// All lines of reflection of `pattern` with at most `max_mismatches` mismatched pairs of cells,
+/// the horizontal ones from top to bottom, then the vertical ones from left to right.
+///
+/// Only the cells with a mirror image inside the pattern have to match; the rows or columns beyond the edge don't count.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::Grid;
+/// use day13::reflection::{find_reflections, Axis, Reflection};
+///
+/// let pattern = Grid::parse("#.##.\n..##.\n##..#", "`.` or `#`", |c| Some(c == '#')).unwrap();
+/// assert_eq!(find_reflections(&pattern, 0), [Reflection { axis: Axis::Vertical, position: 3, smudges: vec![] }]);
+/// assert_eq!(find_reflections(&pattern, 1), [
+///     Reflection { axis: Axis::Horizontal, position: 1, smudges: vec![[(0, 0), (1, 0)]] },
+///     Reflection { axis: Axis::Vertical, position: 1, smudges: vec![[(0, 0), (0, 1)]] },
+///     Reflection { axis: Axis::Vertical, position: 3, smudges: vec![] },
+/// ]);
+/// ```
+pub fn find_reflections<T: Eq>(pattern: &Grid<T>, max_mismatches: usize) -> Vec<Reflection> {
+    let [height, width] = pattern.shape();
+    let horizontal = (1..height).filter_map(|position| {
+        let pairs = (0..position.min(height - position))
+            .flat_map(move |d| (0..width).map(move |x| [(position - 1 - d, x), (position + d, x)]));
+        reflection(pattern, Axis::Horizontal, position, pairs, max_mismatches)
+    });
+    let vertical = (1..width).filter_map(|position| {
+        let pairs = (0..position.min(width - position))
+            .flat_map(move |d| (0..height).map(move |y| [(y, position - 1 - d), (y, position + d)]));
+        reflection(pattern, Axis::Vertical, position, pairs, max_mismatches)
+    });
+    horizontal.chain(vertical).collect()
+}
+
+/// The reflection if at most `max_mismatches` of the mirrored `pairs` differ, stopping at the first one too many.
+fn reflection<T: Eq>(
+    pattern: &Grid<T>,
+    axis: Axis,
+    position: usize,
+    pairs: impl Iterator<Item = [(usize, usize); 2]>,
+    max_mismatches: usize,
+) -> Option<Reflection> {
+    let mut smudges = vec![];
+    for [a, b] in pairs {
+        if pattern[a] != pattern[b] {
+            if smudges.len() == max_mismatches {
+                return None;
+            }
+            smudges.push([a, b]);
+        }
+    }
+    Some(Reflection { axis, position, smudges })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::read_input;
+    use crate::parse_input;
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let pattern = Grid::new(1, 6, vec![1, 2, 3, 4, 4, 3]);
+        assert_eq!(find_reflections(&pattern, 0), [Reflection { axis: Axis::Vertical, position: 4, smudges: vec![] }]);
+
+        let pattern = Grid::new(3, 1, vec![1, 2, 2]);
+        assert_eq!(find_reflections(&pattern, 0), [Reflection { axis: Axis::Horizontal, position: 2, smudges: vec![] }]);
+
+        let one_smudge = |pattern: &Grid<i32>| -> Vec<usize> {
+            find_reflections(pattern, 1).iter().filter(|r| r.mismatches() == 1).map(|r| r.position).collect()
+        };
+        assert_eq!(one_smudge(&Grid::new(3, 2, vec![1, 2, 1, 3, 4, 5])), [1]);
+        assert_eq!(one_smudge(&Grid::new(5, 2, vec![1, 2, 3, 4, 5, 6, 5, 6, 2, 4])), [3]);
+
+        // With equal cells, both lines are perfect reflections; after changing one cell, both are one mismatch away
+        let pattern = Grid::new(2, 2, vec![1, 1, 1, 1]);
+        let reflections = find_reflections(&pattern, 0);
+        assert_eq!(reflections.iter().map(|r| (r.axis, r.position)).collect::<Vec<_>>(), [(Axis::Horizontal, 1), (Axis::Vertical, 1)]);
+        assert_eq!(find_reflections(&Grid::new(2, 2, vec![1, 2, 1, 1]), 0), []);
+        assert_eq!(find_reflections(&Grid::new(2, 2, vec![1, 2, 1, 1]), 1).len(), 2);
+    }
+
+    #[test]
+    fn test_smudges() {
+        let test_input = parse_input(&read_input("test_input.txt").unwrap()).unwrap();
+        let reflections = find_reflections(&test_input.lines[0], 1);
+        assert_eq!(reflections, [
+            Reflection { axis: Axis::Horizontal, position: 3, smudges: vec![[(0, 0), (5, 0)]] },
+            Reflection { axis: Axis::Vertical, position: 5, smudges: vec![] },
+        ]);
+        let reflections = find_reflections(&test_input.lines[1], 1);
+        assert_eq!(reflections, [
+            Reflection { axis: Axis::Horizontal, position: 1, smudges: vec![[(0, 4), (1, 4)]] },
+            Reflection { axis: Axis::Horizontal, position: 4, smudges: vec![] },
+        ]);
+
+        // Every line is a reflection if enough cells may be flipped
+        let reflections = find_reflections(&test_input.lines[1], usize::MAX);
+        assert_eq!(reflections.len(), 6 + 8);
+        assert!(reflections.iter().all(|r| r.smudges.iter().all(|&[a, b]| test_input.lines[1][a] != test_input.lines[1][b])));
+    }
+}
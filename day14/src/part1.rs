@@ -0,0 +1,56 @@
+use crate::{Entry, PuzzleInput};
+
+
+pub fn solve_part1(input: &PuzzleInput) -> u64 {
+    let height = input.platform.height();
+    input.platform.columns().map(|column| solve_column(height, column)).sum()
+}
+
+/// The load on the north support beams of one column, after tilting it north.
+fn solve_column<'a>(height: usize, column: impl Iterator<Item = &'a Entry>) -> u64 {
+    let mut total = 0;
+    // The first position where a round rock can roll to: just below the last cube, or on top of the last rolled rock
+    let mut free = 0;
+    for (i, entry) in column.enumerate() {
+        match entry {
+            Entry::CUBE => free = i + 1,
+            Entry::ROUND => {
+                total += (height - free) as u64;
+                free += 1;
+            }
+            Entry::GROUND => (),
+        }
+    }
+    total
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::{read_input, INPUT_FILE};
+    use aoc::puzzle::read_and_solve_part1;
+    use crate::{parse_input, Day14};
+    use super::*;
+
+    const TEST_SOLUTION: u64 = 136;
+    const TEST_COL_1: u64 = 34;
+    const TEST_COL_2: u64 = 27;
+
+    #[test]
+    fn test_part1() {
+        let test_input = parse_input(&read_input("test_input.txt").unwrap()).unwrap();
+        let height = test_input.platform.height();
+        let output_1 = solve_column(height, test_input.platform.column(0));
+        let output_2 = solve_column(height, test_input.platform.column(1));
+        assert_eq!(output_1, TEST_COL_1);
+        assert_eq!(output_2, TEST_COL_2);
+
+        let test_output = read_and_solve_part1::<Day14>("test_input.txt");
+        assert_eq!(test_output, TEST_SOLUTION);
+    }
+
+    #[bench]
+    fn bench_part1(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part1::<Day14>(INPUT_FILE));
+    }
+}
@@ -0,0 +1,127 @@
+use aoc::aux::{state_at, Grid};
+
+use crate::{Entry, PuzzleInput};
+
+
+const N_REPEATS: usize = 1_000_000_000;
+
+
+pub fn solve_part2(input: &PuzzleInput) -> u64 {
+    score(&state_at(&input.platform, cycle, N_REPEATS))
+}
+
+/// Tilt north, west, south and east, by tilting north and turning the platform clockwise four times.
+fn cycle(platform: &Grid<Entry>) -> Grid<Entry> {
+    let mut platform = platform.clone();
+    for _ in 0..4 {
+        platform = tilt(&platform).rotated_clockwise();
+    }
+    platform
+}
+
+/// Like in part1, but now we actually need to construct the tilted platform
+fn tilt(platform: &Grid<Entry>) -> Grid<Entry> {
+    let mut tilted = platform.clone();
+    for (x, column) in platform.columns().enumerate() {
+        let mut free = 0;
+        for (y, entry) in column.enumerate() {
+            match entry {
+                Entry::CUBE => free = y + 1,
+                Entry::ROUND => {
+                    tilted[(y, x)] = Entry::GROUND;
+                    tilted[(free, x)] = Entry::ROUND;
+                    free += 1;
+                }
+                Entry::GROUND => (),
+            }
+        }
+    }
+    tilted
+}
+
+fn score(platform: &Grid<Entry>) -> u64 {
+    let height = platform.height();
+    platform.rows().enumerate()
+        .map(|(y, row)| (height - y) as u64 * row.iter().filter(|&entry| *entry == Entry::ROUND).count() as u64)
+        .sum()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::{read_input, until_repeat, INPUT_FILE};
+    use aoc::aux::cycles::brent;
+    use aoc::puzzle::read_and_solve_part2;
+    use crate::{parse_input, Day14};
+    use super::*;
+
+    const TEST_SOLUTION: u64 = 64;
+
+    #[test]
+    fn test_part1() {
+        let test_output = read_and_solve_part2::<Day14>("test_input.txt");
+        assert_eq!(test_output, TEST_SOLUTION);
+    }
+
+    #[test]
+    fn test_tilt() {
+        let test_input = parse_input(&read_input("test_input.txt").unwrap()).unwrap();
+        let tilted = tilt(&test_input.platform);
+        let expected = "\
+OOOO.#.O..
+OO..#....#
+OO..O##..O
+O..#.OO...
+........#.
+..#....#.#
+..O..#.O.O
+..O.......
+#....###..
+#....#....\n";
+        assert_eq!(tilted.to_string(), expected);
+    }
+
+    #[test]
+    fn test_cycle() {
+        let test_input = parse_input(&read_input("test_input.txt").unwrap()).unwrap();
+        let cycled = cycle(&test_input.platform);
+        let expected = "\
+.....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#....\n";
+        assert_eq!(cycled.to_string(), expected);
+    }
+
+    #[test]
+    fn test_until_repeat() {
+        let fun = |x: &u64| (x + 1) % 7;
+        let (preamble_len, history) = until_repeat(&3, fun);
+        assert_eq!(preamble_len, 0);
+        assert_eq!(history.len(), 7);
+
+        let (preamble_len, history) = until_repeat(&7, fun);
+        assert_eq!(preamble_len, 1);
+        assert_eq!(history.len(), 8);
+    }
+
+    #[test]
+    fn test_brent() {
+        let test_input = parse_input(&read_input("test_input.txt").unwrap()).unwrap();
+        let info = brent(&test_input.platform, cycle);
+        let (preamble_len, history) = until_repeat(&test_input.platform, cycle);
+        assert_eq!((info.preamble_len, info.cycle_len), (preamble_len, history.len() - preamble_len));
+        assert_eq!(score(&info.state_at(&test_input.platform, cycle, N_REPEATS)), TEST_SOLUTION);
+    }
+
+    #[bench]
+    fn bench_part1(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part2::<Day14>(INPUT_FILE));
+    }
+}
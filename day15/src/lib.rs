@@ -1,22 +1,44 @@
 #![feature(ascii_char)]
+#![cfg_attr(test, feature(test))]
+
+#[cfg(test)]
+extern crate test;
 
 use core::ascii;
-use std::fs;
-use std::io;
-use std::path::Path;
 use std::str::FromStr;
 
-pub const INPUT_FILE: &str = "input.txt";
+use aoc::error::ParseError;
+use aoc::puzzle::Puzzle;
 
+pub mod part1;
+pub mod part2;
 
-pub fn read_input(path: impl AsRef<Path>) -> io::Result<String> {
-    fs::read_to_string(path)
-}
+pub use part1::solve_part1;
+pub use part2::solve_part2;
+
+
+pub struct Day15;
+
+impl Puzzle for Day15 {
+    type Input = PuzzleInput;
+    type Output1 = u64;
+    type Output2 = u64;
+    type ParseError = <PuzzleInput as FromStr>::Err;
+
+    fn parse(input: &str) -> Result<PuzzleInput, Self::ParseError> {
+        parse_input(input)
+    }
 
-pub fn write_output(output: impl ToString) {
-    println!("{}", output.to_string());
+    fn part1(input: &PuzzleInput) -> u64 {
+        solve_part1(input)
+    }
+
+    fn part2(input: &PuzzleInput) -> u64 {
+        solve_part2(input)
+    }
 }
 
+
 pub fn parse_input(input: &str) -> Result<PuzzleInput, <PuzzleInput as FromStr>::Err> {
     input.parse()
 }
@@ -27,12 +49,18 @@ pub struct PuzzleInput {
 }
 
 impl FromStr for PuzzleInput {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let parts: Vec<_> = s.trim().split(',').map(|substr| {
-            substr.as_ascii().unwrap().to_vec()
-        }).collect();
+            match substr.as_ascii() {
+                Some(ascii) => Ok(ascii.to_vec()),
+                None => {
+                    let i = substr.find(|c: char| !c.is_ascii()).unwrap();
+                    Err(ParseError::at_char(substr, i, "an ASCII character").within(s, substr))
+                }
+            }
+        }).collect::<Result<_, _>>()?;
         Ok(PuzzleInput { parts })
     }
 }
@@ -46,28 +74,3 @@ impl std::fmt::Display for PuzzleInput {
         Ok(())
     }
 }
-
-
-/// Parse a whitespace-separated list of numbers
-/// 
-/// # Arguments
-/// 
-/// * `numbers` - A string containing a whitespace-separated list of numbers
-/// 
-/// # Returns
-/// 
-/// A vector of parsed numbers
-/// 
-/// # Example
-/// 
-/// ```
-/// let numbers = "1 2 3 4 5";
-/// let parsed = day15::parse_numbers(numbers);
-/// assert_eq!(parsed, Ok(vec![1, 2, 3, 4, 5]));
-/// ```
-pub fn parse_numbers<T: FromStr>(numbers: &str) -> Result<Vec<T>, <T as FromStr>::Err> {
-    numbers
-        .split_whitespace()
-        .map(|x| x.parse())
-        .collect()
-}
@@ -1,26 +1,9 @@
-#![feature(test, ascii_char)]
-extern crate test;
-
 use core::ascii;
-use std::path::Path;
-
-use day15::{read_input, parse_input, write_output, INPUT_FILE};
-use day15::PuzzleInput;
-
-
-fn main() {
-    let output = read_and_solve(INPUT_FILE).to_string();
-    write_output(output);
-}
 
+use crate::PuzzleInput;
 
-fn read_and_solve(file_path: impl AsRef<Path>) -> u64 {
-    let input = read_input(file_path).unwrap();
-    let input = parse_input(&input).unwrap();
-    solve(&input)
-}
 
-fn solve(input: &PuzzleInput) -> u64 {
+pub fn solve_part1(input: &PuzzleInput) -> u64 {
     input.parts.iter()
     .map(|part| hash_single(part.as_slice()) as u64)
     .sum()
@@ -39,6 +22,9 @@ fn hash_single(string: &[ascii::Char]) -> u8 {
 
 #[cfg(test)]
 mod tests {
+    use aoc::aux::{read_input, INPUT_FILE};
+    use aoc::puzzle::read_and_solve_part1;
+    use crate::{parse_input, Day15};
     use super::*;
 
     const TEST_SOLUTION: u64 = 1320;
@@ -53,12 +39,12 @@ mod tests {
         assert_eq!(output_1, TEST_SOL_1);
         assert_eq!(output_2, TEST_SOL_2);
 
-        let test_output = read_and_solve("test_input.txt");
+        let test_output = read_and_solve_part1::<Day15>("test_input.txt");
         assert_eq!(test_output, TEST_SOLUTION);
     }
 
     #[bench]
     fn bench_part1(b: &mut test::Bencher) {
-        b.iter(|| read_and_solve(INPUT_FILE));
+        b.iter(|| read_and_solve_part1::<Day15>(INPUT_FILE));
     }
 }
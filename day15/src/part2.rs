@@ -1,27 +1,10 @@
-#![feature(test, ascii_char)]
-extern crate test;
-
 use core::ascii;
 use std::array::from_fn;
-use std::path::Path;
-
-use day15::{read_input, parse_input, write_output, INPUT_FILE};
-use day15::PuzzleInput;
-
-
-fn main() {
-    let output = read_and_solve(INPUT_FILE).to_string();
-    write_output(output);
-}
 
+use crate::PuzzleInput;
 
-fn read_and_solve(file_path: impl AsRef<Path>) -> u64 {
-    let input = read_input(file_path).unwrap();
-    let input = parse_input(&input).unwrap();
-    solve(&input)
-}
 
-fn solve(input: &PuzzleInput) -> u64 {
+pub fn solve_part2(input: &PuzzleInput) -> u64 {
     let mut hashmap = Hashmap { boxes: from_fn(|_| vec![]) };
     for part in &input.parts {
         let instruction = Instruction::from_ascii(part).unwrap();
@@ -58,7 +41,7 @@ struct Hashmap {
 impl Hashmap {
     fn perform(&mut self, instruction: &Instruction) {
         match instruction {
-            Instruction::SET(lens) => {
+            Instruction::Set(lens) => {
                 let hash = hash_label(&lens.label);
                 let r#box = &mut self.boxes[hash as usize];
                 let index = r#box.iter().position(|l| l.label == lens.label);
@@ -67,8 +50,8 @@ impl Hashmap {
                     None => { r#box.push(lens.clone()); },
                 }
             },
-            Instruction::REMOVE(label) => {
-                let hash = hash_label(&label);
+            Instruction::Remove(label) => {
+                let hash = hash_label(label);
                 let index = self.boxes[hash as usize].iter().position(|l| l.label == *label);
                 if let Some(index) = index {
                     self.boxes[hash as usize].remove(index);
@@ -86,8 +69,8 @@ struct Lens {
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 enum Instruction {
-    SET(Lens),
-    REMOVE(Box<[ascii::Char]>),
+    Set(Lens),
+    Remove(Box<[ascii::Char]>),
 }
 
 impl Instruction {
@@ -100,9 +83,9 @@ impl Instruction {
         let instruction = match slice[label.len()].to_char() {
             '=' => {
                 let focal_length: u8 = parts[1].as_str().parse().unwrap();
-                Instruction::SET(Lens { focal_length, label })
+                Instruction::Set(Lens { focal_length, label })
             }
-            '-' => Instruction::REMOVE(label),
+            '-' => Instruction::Remove(label),
             _ => return None,
         };
         Some(instruction)
@@ -112,18 +95,20 @@ impl Instruction {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use aoc::aux::INPUT_FILE;
+    use aoc::puzzle::read_and_solve_part2;
+    use crate::Day15;
 
     const TEST_SOLUTION: u64 = 145;
 
     #[test]
     fn test_part2() {
-        let test_output = read_and_solve("test_input.txt");
+        let test_output = read_and_solve_part2::<Day15>("test_input.txt");
         assert_eq!(test_output, TEST_SOLUTION);
     }
 
     #[bench]
     fn bench_part2(b: &mut test::Bencher) {
-        b.iter(|| read_and_solve(INPUT_FILE));
+        b.iter(|| read_and_solve_part2::<Day15>(INPUT_FILE));
     }
 }
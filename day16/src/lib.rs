@@ -1 +1,8 @@
+#![cfg_attr(test, feature(test))]
+
+#[cfg(test)]
+extern crate test;
+
 pub mod today;
+
+pub use today::Day16;
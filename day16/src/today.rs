@@ -3,11 +3,38 @@ use std::str::FromStr;
 
 use itertools::Itertools;
 
+use aoc::aux::Grid;
+use aoc::aux::geometry::{Direction, Orientation};
+use aoc::error::ParseError;
+use aoc::puzzle::Puzzle;
+
 
 pub type Solution1 = usize;
 pub type Solution2 = usize;
 
 
+pub struct Day16;
+
+impl Puzzle for Day16 {
+    type Input = PuzzleInput;
+    type Output1 = Solution1;
+    type Output2 = Solution2;
+    type ParseError = <PuzzleInput as FromStr>::Err;
+
+    fn parse(input: &str) -> Result<PuzzleInput, Self::ParseError> {
+        input.parse()
+    }
+
+    fn part1(input: &PuzzleInput) -> Solution1 {
+        solve_part1(input)
+    }
+
+    fn part2(input: &PuzzleInput) -> Solution2 {
+        solve_part2(input)
+    }
+}
+
+
 pub fn solve_part1(input: &PuzzleInput) -> Solution1 {
     let starting_cursor = Cursor { y: 0, x: 0, direction: Direction::Right };
     let starting_state = State::new(starting_cursor);
@@ -16,14 +43,14 @@ pub fn solve_part1(input: &PuzzleInput) -> Solution1 {
 
 pub fn solve_part2(input: &PuzzleInput) -> Solution2 {
     let candidates = [
-        (Direction::Right, (0..input.map.nrows()), (0..1)),
-        (Direction::Left, (0..input.map.nrows()), (input.map.ncols() - 1..input.map.ncols())),
-        (Direction::Down, (0..1), (0..input.map.ncols())),
-        (Direction::Up, (input.map.nrows() - 1..input.map.nrows()), (0..input.map.ncols())),
+        (Direction::Right, (0..input.map.height()), (0..1)),
+        (Direction::Left, (0..input.map.height()), (input.map.width() - 1..input.map.width())),
+        (Direction::Down, (0..1), (0..input.map.width())),
+        (Direction::Up, (input.map.height() - 1..input.map.height()), (0..input.map.width())),
     ];
     let starting_cursors = candidates.map(|(direction, y_range, x_range)| {
         y_range.cartesian_product(x_range).map(move |(y, x)| {
-            Cursor { y, x, direction: direction.clone() }
+            Cursor { y, x, direction }
         })
     }).into_iter().flatten();
     starting_cursors.map(|cursor| {
@@ -32,7 +59,7 @@ pub fn solve_part2(input: &PuzzleInput) -> Solution2 {
     }).max().unwrap()
 }
 
-fn solve_single(mut state: State, map: &ndarray::Array2<Symbol>) -> usize {
+fn solve_single(mut state: State, map: &Grid<Symbol>) -> usize {
     while !state.current_cursors.is_empty() {
         step(&mut state, map);
     }
@@ -42,19 +69,14 @@ fn solve_single(mut state: State, map: &ndarray::Array2<Symbol>) -> usize {
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PuzzleInput {
-    map: ndarray::Array2<Symbol>,
+    map: Grid<Symbol>,
 }
 
 impl FromStr for PuzzleInput {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<PuzzleInput, Self::Err> {
-        let lines: Vec<_> = s.lines().collect();
-        let height = lines.len();
-        let width = lines[0].len();
-        let symbols: Result<Vec<_>, _> = lines.join("").chars().map(Symbol::from_char).collect();
-        let symbols = symbols?;
-        let map = ndarray::Array2::from_shape_vec((height, width), symbols).unwrap();
+        let map = Grid::parse(s, "one of `.|-/\\`", Symbol::from_char)?;
         Ok(PuzzleInput { map })
     }
 }
@@ -82,12 +104,12 @@ impl std::hash::Hash for State {
     }
 }
 
-fn step(state: &mut State, map: &ndarray::Array2<Symbol>) {
+fn step(state: &mut State, map: &Grid<Symbol>) {
     let mut next_cursors: Vec<Cursor> = Vec::new();
     for cursor in &state.current_cursors {
         let next = advance_cursor(map, cursor);
         for n in next.iter() {
-            if state.history.contains(&n) {
+            if state.history.contains(n) {
                 continue;
             }
             state.history.insert(n.clone());
@@ -97,48 +119,19 @@ fn step(state: &mut State, map: &ndarray::Array2<Symbol>) {
     state.current_cursors = next_cursors;
 }
 
-fn advance_cursor(map: &ndarray::Array2<Symbol>, cursor: &Cursor) -> Vec<Cursor> {
-    let (dy, dx) = cursor.direction.as_vector();
-    let (y, x) = (cursor.y as isize + dy, cursor.x as isize + dx);
-    let (y, x) = (usize::try_from(y), usize::try_from(x));
-    let (y, x) = match (y, x) {
-        (Ok(y), Ok(x)) => (y, x),
-        _ => return vec![],  // out of bounds
+fn advance_cursor(map: &Grid<Symbol>, cursor: &Cursor) -> Vec<Cursor> {
+    let Some((y, x)) = map.step((cursor.y, cursor.x), cursor.direction) else {
+        return vec![];  // out of bounds
     };
-    let symbol = map.get((y, x));
-    let directions = match symbol {
-        None => vec![],  // out of bounds
-        Some(Symbol::Empty) => vec![cursor.direction.clone()],
-        Some(Symbol::SplitV) => match cursor.direction {
-            Direction::Left | Direction::Right => {
-                vec![
-                    Direction::Up,
-                    Direction::Down
-                ]
-            } 
-            Direction::Up | Direction::Down => vec![cursor.direction.clone()],
-        },
-        Some(Symbol::SplitH) => match cursor.direction {
-            Direction::Up | Direction::Down => {
-                vec![
-                    Direction::Left,
-                    Direction::Right
-                ]
-            } 
-            Direction::Left | Direction::Right => vec![cursor.direction.clone()],
-        },
-        Some(Symbol::MirrorUlDr) => match cursor.direction {
-            Direction::Up => vec![Direction::Left],
-            Direction::Right => vec![Direction::Down],
-            Direction::Down => vec![Direction::Right],
-            Direction::Left => vec![Direction::Up],
-        },
-        Some(Symbol::MirrorDlUr) => match cursor.direction {
-            Direction::Up => vec![Direction::Right],
-            Direction::Right => vec![Direction::Up],
-            Direction::Down => vec![Direction::Left],
-            Direction::Left => vec![Direction::Down],
-        },
+    let direction = cursor.direction;
+    let directions = match (&map[(y, x)], direction.orientation()) {
+        (Symbol::Empty, _) => vec![direction],
+        (Symbol::SplitV, Orientation::Horizontal) => Orientation::Vertical.directions().to_vec(),
+        (Symbol::SplitH, Orientation::Vertical) => Orientation::Horizontal.directions().to_vec(),
+        (Symbol::SplitV | Symbol::SplitH, _) => vec![direction],
+        // `\` turns vertical beams to their left and horizontal beams to their right, `/` the other way around
+        (Symbol::MirrorUlDr, Orientation::Vertical) | (Symbol::MirrorDlUr, Orientation::Horizontal) => vec![direction.turn_left()],
+        (Symbol::MirrorUlDr, Orientation::Horizontal) | (Symbol::MirrorDlUr, Orientation::Vertical) => vec![direction.turn_right()],
     };
     directions.into_iter().map(|direction| Cursor { y, x, direction }).collect()
 }
@@ -151,39 +144,52 @@ struct Cursor {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
+enum Symbol {
+    Empty, SplitV, SplitH, MirrorUlDr, MirrorDlUr,
 }
 
-impl Direction {
-    fn as_vector(&self) -> (isize, isize) {
-        match self {
-            Direction::Up => (-1, 0),
-            Direction::Down => (1, 0),
-            Direction::Left => (0, -1),
-            Direction::Right => (0, 1),
+impl Symbol {
+    fn from_char(c: char) -> Option<Symbol> {
+        match c {
+            '.' => Some(Symbol::Empty),
+            '|' => Some(Symbol::SplitV),
+            '-' => Some(Symbol::SplitH),
+            '/' => Some(Symbol::MirrorDlUr),
+            '\\' => Some(Symbol::MirrorUlDr),
+            _ => None,
         }
     }
 }
 
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
-enum Symbol {
-    Empty, SplitV, SplitH, MirrorUlDr, MirrorDlUr,
-}
+#[cfg(test)]
+mod tests {
+    use aoc::aux::{INPUT_FILE, TEST_INPUT_FILE};
+    use aoc::puzzle::{read_and_solve_part1, read_and_solve_part2};
+    use super::*;
 
-impl Symbol {
-    fn from_char(c: char) -> Result<Symbol, String> {
-        match c {
-            '.' => Ok(Symbol::Empty),
-            '|' => Ok(Symbol::SplitV),
-            '-' => Ok(Symbol::SplitH),
-            '/' => Ok(Symbol::MirrorDlUr),
-            '\\' => Ok(Symbol::MirrorUlDr),
-            _ => Err(format!("Invalid character: {}", c)),
-        }
+    const TEST_SOLUTION_1: Solution1 = 46;
+    const TEST_SOLUTION_2: Solution2 = 51;
+
+    #[test]
+    fn test_part1() {
+        let test_output = read_and_solve_part1::<Day16>(TEST_INPUT_FILE);
+        assert_eq!(test_output, TEST_SOLUTION_1);
+    }
+
+    #[test]
+    fn test_part2() {
+        let test_output = read_and_solve_part2::<Day16>(TEST_INPUT_FILE);
+        assert_eq!(test_output, TEST_SOLUTION_2);
+    }
+
+    #[bench]
+    fn bench_part1(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part1::<Day16>(INPUT_FILE));
+    }
+
+    #[bench]
+    fn bench_part2(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part2::<Day16>(INPUT_FILE));
     }
 }
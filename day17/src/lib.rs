@@ -1,2 +1,8 @@
+#![cfg_attr(test, feature(test))]
+
+#[cfg(test)]
+extern crate test;
+
 pub mod today;
-mod aux;
+
+pub use today::Day17;
@@ -1,77 +1,145 @@
 use std::cmp::{max, min};
-use std::collections::{BinaryHeap, HashMap};
-use std::fmt;
 use std::str::FromStr;
 
 use itertools::iproduct;
-use ndarray::Array2;
 
-use crate::aux::aux;
+use aoc::aux::Grid;
+use aoc::aux::geometry::{Direction, Orientation, Point};
+use aoc::aux::search::astar;
+use aoc::error::ParseError;
+use aoc::puzzle::Puzzle;
 
 
 pub type Solution1 = Cost;
 pub type Solution2 = u64;
-type Cost = u64;
+pub type Cost = u64;
+
+
+pub struct Day17;
+
+impl Puzzle for Day17 {
+    type Input = PuzzleInput;
+    type Output1 = Solution1;
+    type Output2 = Solution2;
+    type ParseError = <PuzzleInput as FromStr>::Err;
+
+    fn parse(input: &str) -> Result<PuzzleInput, Self::ParseError> {
+        input.parse()
+    }
+
+    fn part1(input: &PuzzleInput) -> Solution1 {
+        solve_part1(input)
+    }
+
+    fn part2(input: &PuzzleInput) -> Solution2 {
+        solve_part2(input)
+    }
+}
 
 
 // Solution based on A* search. The heuristic is the Manhattan distance
-// The nodes in the graph are the coordinates on the map, together with the orientation (horizontal or vertical) of the previous step.
-// A transition from one node to another is a move of 1, 2 or 3 steps in a direction orthogonal to the previous step.
-// Note that this means that each coordinate corresponds to 2 different nodes in the graph.
+// The nodes in the graph are the coordinates on the map, together with the direction of the previous run.
+// A transition from one node to another is a whole run: between `min_run` and `max_run` steps in a direction orthogonal to the previous run
+// (or opposite to it, if U-turns are allowed).
+// Note that this means that each coordinate corresponds to up to 4 different nodes in the graph.
 // We don't need to store all possible nodes explicitly, because A* will never visit some of them if the heuristic is good enough.
 
 
 pub fn solve_part1(input: &PuzzleInput) -> Solution1 {
-    solve_for_arbitrary_len(input, &[1, 2, 3])
+    find_route(input, &CrucibleRules::crucible()).expect("No path to goal found").heat_loss
 }
 
 pub fn solve_part2(input: &PuzzleInput) -> Solution2 {
-    solve_for_arbitrary_len(input, &[4, 5, 6, 7, 8, 9, 10])
-}
-
-fn solve_for_arbitrary_len(input: &PuzzleInput, lengths: &[usize]) -> Solution1 {
-    let start_v = Node { y: 0, x: 0, prev_orientation: Orientation::Vertical };
-    let start_h = Node { y: 0, x: 0, prev_orientation: Orientation::Horizontal };
-    let goal = (input.map.shape()[0] - 1, input.map.shape()[1] - 1);
-    let mut fringe: BinaryHeap<HeapEntry> = [
-        HeapEntry { node: start_v.clone(), f_val: heuristic(&start_v, &input) },
-        HeapEntry { node: start_h.clone(), f_val: heuristic(&start_h, &input) },
-    ].into();
-    let mut cost_so_far: HashMap<Node, Cost> = [(start_v, 0), (start_h, 0)].into();
-    while let Some(HeapEntry { node, f_val: _ }) = fringe.pop() {
-        if (node.y, node.x) == goal {
-            return cost_so_far[&node];
-        }
-        let neighbors = neighbors(&node, &input, lengths.iter().copied());
-        for neighbor in neighbors {
-            let intermed = intermediaries((&node.y, &node.x), (&neighbor.y, &neighbor.x)).expect("Failed to find intermediaries");
-            let add_cost: Cost = intermed.into_iter().map(|(y, x)| input.map[[y, x]] as Cost).sum();
-            let new_cost = cost_so_far[&node] + add_cost;
-            let prev_cost = cost_so_far.get(&neighbor).copied().unwrap_or(Cost::MAX);
-            if new_cost < prev_cost {
-                cost_so_far.insert(neighbor.clone(), new_cost);
-                let f_val = new_cost + heuristic(&neighbor, &input);
-                fringe.push(HeapEntry { node: neighbor.clone(), f_val });
-            }
+    find_route(input, &CrucibleRules::ultra_crucible()).expect("No path to goal found").heat_loss
+}
+
+/// The rules by which a crucible moves through the map.
+///
+/// # Example
+///
+/// ```
+/// use day17::today::{find_route, CrucibleRules, PuzzleInput};
+///
+/// let input: PuzzleInput = "11111".parse().unwrap();
+/// // The crucible can't go straight for more than 3 blocks, and can't turn in a single row
+/// assert_eq!(find_route(&input, &CrucibleRules::crucible()), None);
+/// let rules = CrucibleRules { u_turns: true, ..CrucibleRules::crucible() };
+/// assert_eq!(find_route(&input, &rules).unwrap().heat_loss, 6);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CrucibleRules {
+    /// The minimum number of blocks the crucible moves in a direction before it can turn
+    pub min_run: usize,
+    /// The maximum number of blocks the crucible moves in a direction before it has to turn
+    pub max_run: usize,
+    /// Whether the crucible can turn around instead of turning left or right
+    pub u_turns: bool,
+    /// Whether the crucible can only stop at the goal after a run of at least `min_run` blocks
+    pub finish_with_min_run: bool,
+    /// The orientations in which the first run can go
+    pub start_orientations: Vec<Orientation>,
+    pub start: (usize, usize),
+    /// The bottom right corner of the map if `None`
+    pub goal: Option<(usize, usize)>,
+}
+
+impl CrucibleRules {
+    /// The rules of part 1
+    pub fn crucible() -> CrucibleRules {
+        CrucibleRules {
+            min_run: 1,
+            max_run: 3,
+            u_turns: false,
+            finish_with_min_run: true,
+            start_orientations: vec![Orientation::Horizontal, Orientation::Vertical],
+            start: (0, 0),
+            goal: None,
         }
     }
-    panic!("No path to goal found");
+
+    /// The rules of part 2
+    pub fn ultra_crucible() -> CrucibleRules {
+        CrucibleRules { min_run: 4, max_run: 10, ..CrucibleRules::crucible() }
+    }
+
+    fn goal(&self, map: &Grid<u8>) -> (usize, usize) {
+        self.goal.unwrap_or((map.height() - 1, map.width() - 1))
+    }
+}
+
+/// Find the route with the least heat loss from the start to the goal, or `None` if the rules don't allow reaching the goal.
+pub fn find_route(input: &PuzzleInput, rules: &CrucibleRules) -> Option<Route> {
+    let start = Node { y: rules.start.0, x: rules.start.1, prev_direction: None };
+    let goal = rules.goal(&input.map);
+    let successors = |node: &Node| {
+        let (y, x) = (node.y, node.x);
+        neighbors(node, input, rules).into_iter().map(move |neighbor| {
+            let intermed = intermediaries((&y, &x), (&neighbor.y, &neighbor.x)).expect("Failed to find intermediaries");
+            let add_cost: Cost = intermed.into_iter().map(|(y, x)| input.map[(y, x)] as Cost).sum();
+            (neighbor, add_cost)
+        })
+    };
+    let tree = astar([start], successors, |node| heuristic(node, goal), |node| (node.y, node.x) == goal);
+    let result = tree.result()?;
+    Some(Route::new(input, result.cost, result.path))
 }
 
-fn heuristic(node: &Node, input: &PuzzleInput) -> Cost {
-    let shape = input.map.shape();
-    (shape[0] + shape[1] - (node.y + node.x)) as Cost
+fn heuristic(node: &Node, goal: (usize, usize)) -> Cost {
+    (node.y.abs_diff(goal.0) + node.x.abs_diff(goal.1)) as Cost
 }
 
-fn neighbors(node: &Node, input: &PuzzleInput, len_candidates: impl Iterator<Item = usize>) -> Vec<Node> {
-    let dir_candidates = node.prev_orientation.orthogonals();
-    iproduct!(len_candidates, dir_candidates).filter_map(|(new_len, new_dir)| {
-        let new_y = node.y as isize + new_dir.dy() * new_len as isize;
-        let new_x = node.x as isize + new_dir.dx() * new_len as isize;
-        let shape: [usize; 2] = input.map.shape().try_into().unwrap();
-        if let Some([new_y, new_x]) = aux::within_bounds([new_y, new_x], shape) {
-            Some(Node { y: new_y, x: new_x, prev_orientation: new_dir.orientation() })
-        } else { None }
+fn neighbors(node: &Node, input: &PuzzleInput, rules: &CrucibleRules) -> Vec<Node> {
+    let dir_candidates: Vec<Direction> = match node.prev_direction {
+        None => rules.start_orientations.iter().flat_map(|orientation| orientation.directions()).collect(),
+        Some(prev_direction) if rules.u_turns => [prev_direction.orientation().orthogonals().as_slice(), &[prev_direction.reverse()]].concat(),
+        Some(prev_direction) => prev_direction.orientation().orthogonals().to_vec(),
+    };
+    let goal = rules.goal(&input.map);
+    iproduct!(1..=rules.max_run, dir_candidates).filter_map(|(new_len, new_dir)| {
+        let target = Point::from_index((node.y, node.x)) + new_dir.as_vector() * new_len as isize;
+        let (new_y, new_x) = target.to_index(input.map.shape())?;
+        let stops_early = new_len < rules.min_run && (rules.finish_with_min_run || (new_y, new_x) != goal);
+        (!stops_early).then_some(Node { y: new_y, x: new_x, prev_direction: Some(new_dir) })
     }).collect()
 }
 
@@ -96,116 +164,159 @@ fn intermediaries((y1, x1): (&usize, &usize), (y2, x2): (&usize, &usize)) -> Res
     Err(())
 }
 
-// This bundles a node together with its f value (guess of total cost from start to goal through this node) so that the priority queue can sort nodes by f value
-#[derive(Clone, Debug, PartialEq, Eq)]
-struct HeapEntry {
-    node: Node,
-    f_val: Cost,
-}
-
-// We sort by f value, plus some arbitrary tiebreaker. Doesn't matter much as long as all ties are broken consistently.
-impl PartialOrd for HeapEntry {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(
-            other.f_val.cmp(&self.f_val)
-                .then(self.node.y.cmp(&other.node.y))
-                .then(self.node.x.cmp(&other.node.x))
-                .then(self.node.prev_orientation.cmp(&other.node.prev_orientation))
-        )
-    }
-}
-
-impl Ord for HeapEntry {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
-    }
-}
-
+/// A position of the crucible, together with the direction of the run that brought it there.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-struct Node {
-    y: usize,
-    x: usize,
-    prev_orientation: Orientation,
-}
-
-#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
-enum Orientation {
-    Horizontal,
-    Vertical,
+pub struct Node {
+    pub y: usize,
+    pub x: usize,
+    /// `None` at the start, before the first run
+    pub prev_direction: Option<Direction>,
 }
 
-impl Orientation {
-    fn orthogonals(&self) -> [Direction; 2] {
-        match self {
-            Self::Vertical => [Direction::Left, Direction::Right],
-            Self::Horizontal => [Direction::Up, Direction::Down],
-        }
-    }
+/// A route of the crucible through the map.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Route {
+    pub heat_loss: Cost,
+    /// The start, every point where the crucible turns, and the goal
+    pub nodes: Vec<Node>,
+    /// Every block the crucible enters, in order
+    pub steps: Vec<RouteStep>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, Copy)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
+/// A single block entered by the crucible.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RouteStep {
+    pub y: usize,
+    pub x: usize,
+    /// The direction in which the block was entered
+    pub direction: Direction,
+    /// The heat lost in this block
+    pub heat: Cost,
 }
 
-impl Direction {
-    fn dy(&self) -> isize {
-        match self {
-            Direction::Up => -1,
-            Direction::Down => 1,
-            _ => 0,
-        }
-    }
-
-    fn dx(&self) -> isize {
-        match self {
-            Direction::Left => -1,
-            Direction::Right => 1,
-            _ => 0,
-        }
+impl Route {
+    fn new(input: &PuzzleInput, heat_loss: Cost, nodes: Vec<Node>) -> Route {
+        let steps = nodes.windows(2).flat_map(|pair| {
+            let (from, to) = (&pair[0], &pair[1]);
+            let direction = (Point::from_index((to.y, to.x)) - Point::from_index((from.y, from.x))).direction()
+                .expect("Consecutive nodes must differ");
+            let intermed = intermediaries((&from.y, &from.x), (&to.y, &to.x)).expect("Failed to find intermediaries");
+            intermed.into_iter().map(move |(y, x)| RouteStep { y, x, direction, heat: input.map[(y, x)] as Cost })
+        }).collect();
+        Route { heat_loss, nodes, steps }
     }
 
-    fn orientation(&self) -> Orientation {
-        match self {
-            Direction::Up | Direction::Down => Orientation::Vertical,
-            Direction::Left | Direction::Right => Orientation::Horizontal,
+    /// Draw the map with every block of the route replaced by an arrow (`>v<^`) in the direction it was entered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use day17::today::{find_route, CrucibleRules, PuzzleInput};
+    ///
+    /// let input: PuzzleInput = "111\n991\n991".parse().unwrap();
+    /// let route = find_route(&input, &CrucibleRules::crucible()).unwrap();
+    /// assert_eq!(route.heat_loss, 4);
+    /// assert_eq!(route.render(&input), "1>>\n99v\n99v\n");
+    /// ```
+    pub fn render(&self, input: &PuzzleInput) -> String {
+        let mut canvas = input.map.map(|&heat| char::from_digit(heat as u32, 10).unwrap());
+        for step in &self.steps {
+            canvas[(step.y, step.x)] = step.direction.as_arrow();
         }
+        canvas.to_string()
     }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PuzzleInput {
-    map: Array2<u8>,
+    map: Grid<u8>,
 }
 
 impl FromStr for PuzzleInput {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<PuzzleInput, Self::Err> {
-        let vecs: Result<Vec<Vec<u8>>, _> = s.lines().map(|line| {
-            line.chars().map(|c| c.to_string().parse()).collect()
-        }).collect();
-        let vecs = vecs.map_err(ParseError::ParseIntError)?;
-        let (height, width) = (vecs.len(), vecs[0].len());
-        let flat_vec = vecs.into_iter().flatten().collect();
-        let map = Array2::from_shape_vec((height, width), flat_vec).map_err(ParseError::ShapeError)?;
+        let map = Grid::parse(s, "a digit", |c| c.to_digit(10).map(|d| d as u8))?;
         Ok(PuzzleInput { map })
     }
 }
 
-pub enum ParseError {
-    ParseIntError(std::num::ParseIntError),
-    ShapeError(ndarray::ShapeError),
-}
 
-impl fmt::Display for ParseError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            ParseError::ParseIntError(err) => write!(f, "Error parsing integer: {}", err),
-            ParseError::ShapeError(err) => write!(f, "Error creating array from shape and data: {}", err),
-        }
+#[cfg(test)]
+mod tests {
+    use aoc::aux::{read_input, INPUT_FILE, TEST_INPUT_FILE};
+    use aoc::puzzle::{read_and_solve_part1, read_and_solve_part2};
+    use super::*;
+
+    const TEST_SOLUTION_1: Solution1 = 102;
+    const TEST_SOLUTION_2: Solution2 = 94;
+
+    #[test]
+    fn test_part1() {
+        let test_output = read_and_solve_part1::<Day17>(TEST_INPUT_FILE);
+        assert_eq!(test_output, TEST_SOLUTION_1);
+    }
+
+    #[test]
+    fn test_part2() {
+        let test_output = read_and_solve_part2::<Day17>(TEST_INPUT_FILE);
+        assert_eq!(test_output, TEST_SOLUTION_2);
+    }
+
+    #[test]
+    fn test_route() {
+        let input: PuzzleInput = read_input(TEST_INPUT_FILE).unwrap().parse().unwrap();
+        let route = find_route(&input, &CrucibleRules::crucible()).unwrap();
+        assert_eq!(route.heat_loss, TEST_SOLUTION_1);
+        assert_eq!(route.steps.iter().map(|step| step.heat).sum::<Cost>(), route.heat_loss);
+        assert_eq!(route.nodes.first().map(|node| (node.y, node.x)), Some((0, 0)));
+        assert_eq!(route.nodes.last().map(|node| (node.y, node.x)), Some((12, 12)));
+        assert_eq!(route.steps.last().map(|step| (step.y, step.x)), Some((12, 12)));
+        let expected = "\
+2>>34^>>>1323
+32v>>>35v5623
+32552456v>>54
+3446585845v52
+4546657867v>6
+14385987984v4
+44578769877v6
+36378779796v>
+465496798688v
+456467998645v
+12246868655<v
+25465488877v5
+43226746555v>
+";
+        assert_eq!(route.render(&input), expected);
+    }
+
+    #[test]
+    fn test_rules() {
+        let input: PuzzleInput = "111111111111\n999999999991\n999999999991\n999999999991\n999999999991".parse().unwrap();
+        let route = find_route(&input, &CrucibleRules::ultra_crucible()).unwrap();
+        assert_eq!(route.heat_loss, 71);
+        let rules = CrucibleRules { finish_with_min_run: false, ..CrucibleRules::ultra_crucible() };
+        let route = find_route(&input, &rules).unwrap();
+        assert_eq!(route.heat_loss, 47);
+
+        let input: PuzzleInput = read_input(TEST_INPUT_FILE).unwrap().parse().unwrap();
+        let rules = CrucibleRules { start_orientations: vec![Orientation::Vertical], ..CrucibleRules::crucible() };
+        let route = find_route(&input, &rules).unwrap();
+        assert_eq!(route.steps[0].direction, Direction::Down);
+        assert_eq!(route.heat_loss, TEST_SOLUTION_1);
+        let rules = CrucibleRules { start: (12, 12), goal: Some((0, 0)), ..CrucibleRules::crucible() };
+        let route = find_route(&input, &rules).unwrap();
+        assert_eq!(route.nodes.last().map(|node| (node.y, node.x)), Some((0, 0)));
+        assert_eq!(route.heat_loss, 101);
+    }
+
+    #[bench]
+    fn bench_part1(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part1::<Day17>(INPUT_FILE));
+    }
+
+    #[bench]
+    fn bench_part2(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part2::<Day17>(INPUT_FILE));
     }
 }
@@ -1,3 +1,8 @@
-#![feature(step_trait)]
+#![cfg_attr(test, feature(test))]
+
+#[cfg(test)]
+extern crate test;
 
 pub mod today;
+
+pub use today::Day18;
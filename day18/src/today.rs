@@ -1,14 +1,38 @@
 use std::cmp::{min, max};
-use std::iter::Step;
-use std::{fmt, vec};
-use std::ops::RangeInclusive;
 use std::str::FromStr;
 
+use aoc::aux::geometry::{Direction, Point};
+use aoc::aux::intervals::IntervalSet;
+use aoc::error::{parse_at, ParseError};
+use aoc::puzzle::Puzzle;
+
 
 pub type Solution1 = u64;
 pub type Solution2 = u64;
 
 
+pub struct Day18;
+
+impl Puzzle for Day18 {
+    type Input = PuzzleInput;
+    type Output1 = Solution1;
+    type Output2 = Solution2;
+    type ParseError = <PuzzleInput as FromStr>::Err;
+
+    fn parse(input: &str) -> Result<PuzzleInput, Self::ParseError> {
+        input.parse()
+    }
+
+    fn part1(input: &PuzzleInput) -> Solution1 {
+        solve_part1(input)
+    }
+
+    fn part2(input: &PuzzleInput) -> Solution2 {
+        solve_part2(input)
+    }
+}
+
+
 pub fn solve_part1(input: &PuzzleInput) -> Solution1 {
     let (v_inner, v_outer, h_edges) = preprocess_edges(&input.edges);
     let mut area = input.edges.iter().map(|dig| dig.distance as Solution1).sum();
@@ -24,17 +48,16 @@ pub fn solve_part1(input: &PuzzleInput) -> Solution1 {
             if y2_on_h_edge {
                 y2 -= 1;
             }
-            (y1, y2)
+            IntervalSet::from_inclusive(y1..=y2)
         };
-        let mut covered = RangeUnion { ranges: vec![] };
+        let mut covered = IntervalSet::new();
         let outer_start = v_outer.binary_search_by_key(&x_inner, |(x, _, _)| *x)
             .unwrap_or_else(|i| i);
         for (x_outer, y1, y2) in v_outer[outer_start..].iter().copied() {
-            let intersection = intersect_ranges((y1, y2), y_range);
-            if let Some(intersection) = intersection {
-                let n_covered = covered.update(&intersection) as Solution1;
-                area += n_covered * (x_outer - x_inner - 1) as Solution1;
-            }
+            let intersection = IntervalSet::from_inclusive(y1..=y2).intersection(&y_range);
+            let n_covered = intersection.difference(&covered).len() as Solution1;
+            covered = covered.union(&intersection);
+            area += n_covered * (x_outer - x_inner - 1) as Solution1;
         }
     }
     area
@@ -53,23 +76,23 @@ pub fn solve_part2(input: &PuzzleInput) -> Solution2 {
             _ => panic!("Invalid direction"),
         };
         Dig {
-            direction: direction,
-            distance: distance,
+            direction,
+            distance,
             color: None,
         }
     }).collect();
-    dbg!(&translated);
     let new_input = PuzzleInput { edges: translated };
     solve_part1(&new_input)
 }
 
+/// An axis-aligned edge `(position, start, end)`, e.g. `(x, y1, y2)` for a vertical edge.
+type Edge = (isize, isize, isize);
+
 /// Find the vertical and horizontal edges and detect on which side of the map they are.
-fn preprocess_edges(edges: &[Dig]) -> (Vec<(isize, isize, isize)>, Vec<(isize, isize, isize)>, Vec<(isize, isize, isize)>) {
-    let coos = edges.iter().fold(vec![(0, 0)], |mut coos, dig| {
-        let (y, x) = coos.last().unwrap();
-        let y = y + dig.direction.dy() * dig.distance as isize;
-        let x = x + dig.direction.dx() * dig.distance as isize;
-        coos.push((y, x));
+fn preprocess_edges(edges: &[Dig]) -> (Vec<Edge>, Vec<Edge>, Vec<Edge>) {
+    let coos = edges.iter().fold(vec![Point::ORIGIN], |mut coos, dig| {
+        let last = *coos.last().unwrap();
+        coos.push(last + dig.direction.as_vector() * dig.distance as isize);
         coos
     });
 
@@ -79,16 +102,16 @@ fn preprocess_edges(edges: &[Dig]) -> (Vec<(isize, isize, isize)>, Vec<(isize, i
     for (dig, edge) in edges.iter().zip(coos.windows(2)) {
         match dig.direction {
             Direction::Up | Direction::Down => {
-                let x = edge[0].1;
-                let y1 = min(edge[0].0, edge[1].0);
-                let y2 = max(edge[0].0, edge[1].0);
+                let x = edge[0].x;
+                let y1 = min(edge[0].y, edge[1].y);
+                let y2 = max(edge[0].y, edge[1].y);
                 v_edges.push((x, y1, y2));
-                v_dirs.push(dig.direction.clone());
+                v_dirs.push(dig.direction);
             }
             Direction::Left | Direction::Right => {
-                let y = edge[0].0;
-                let x1 = min(edge[0].1, edge[1].1);
-                let x2 = max(edge[0].1, edge[1].1);
+                let y = edge[0].y;
+                let x1 = min(edge[0].x, edge[1].x);
+                let x2 = max(edge[0].x, edge[1].x);
                 h_edges.push((y, x1, x2));
             }
         }
@@ -99,15 +122,15 @@ fn preprocess_edges(edges: &[Dig]) -> (Vec<(isize, isize, isize)>, Vec<(isize, i
         v_argsort
     };
     v_edges = v_argsort.iter().map(|&i| v_edges[i]).collect();
-    v_dirs = v_argsort.iter().map(|&i| v_dirs[i].clone()).collect();
+    v_dirs = v_argsort.iter().map(|&i| v_dirs[i]).collect();
     h_edges.sort();
-    let inner_dir = v_dirs[0].clone();
+    let inner_dir = v_dirs[0];
     let (v_inner, v_outer) = v_edges.iter().zip(v_dirs.iter())
         .fold((vec![], vec![]), |(mut inner, mut outer), (edge, dir)| {
             if dir == &inner_dir {
-                inner.push(edge.clone());
+                inner.push(*edge);
             } else {
-                outer.push(edge.clone());
+                outer.push(*edge);
             }
             (inner, outer)
         }
@@ -115,74 +138,43 @@ fn preprocess_edges(edges: &[Dig]) -> (Vec<(isize, isize, isize)>, Vec<(isize, i
     (v_inner, v_outer, h_edges)
 }
 
-fn intersect_ranges<T: Ord>((a1, a2): (T, T), (b1, b2): (T, T)) -> Option<RangeInclusive<T>> {
-    let a1 = max(a1, b1);
-    let a2 = min(a2, b2);
-    if a1 <= a2 {
-        Some(a1..=a2)
-    } else {
-        None
-    }
-}
-
-fn merge_ranges<T: Ord + Step>(sorted_ranges: &[RangeInclusive<T>]) -> Vec<RangeInclusive<T>> {
-    sorted_ranges.iter().fold(Vec::new(), |mut merged, range| {
-        if let Some(last) = merged.last_mut() {
-            if *last.end() >= T::backward(range.start().clone(), 1) {
-                *last = last.start().clone()..=max(last.end(), range.end()).clone();
-                return merged;
-            }
-        }
-        merged.push(range.clone());
-        merged
-    })
-}
-
-#[derive(Debug)]
-struct RangeUnion<T: Ord> {
-    /// A vector of non-overlapping ranges sorted by start
-    ranges: Vec<RangeInclusive<T>>,
-}
-
-impl RangeUnion<isize> {
-    fn update(&mut self, other: &RangeInclusive<isize>) -> isize {
-        let old_size: isize = self.ranges.iter().map(|range| range.end() - range.start() + 1).sum();
-        let i = self.ranges.binary_search_by_key(&other.start(), |range| range.start()).unwrap_or_else(|i| i);
-        self.ranges.insert(i, other.clone());
-        self.ranges = merge_ranges(&self.ranges);
-        let new_size: isize = self.ranges.iter().map(|range| range.end() - range.start() + 1).sum();
-        new_size - old_size
-    }
-}
-
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PuzzleInput {
     edges: Vec<Dig>,
 }
 
 impl FromStr for PuzzleInput {
-    type Err = InputParseError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<PuzzleInput, Self::Err> {
         let digs: Result<Vec<Dig>, _> = s.lines().map(|line| {
-            let mut parts = line.split(' ');
-            let direction = parts.next().ok_or(InputParseError::StringParseError)?;
-            let direction = direction.chars().next().ok_or(InputParseError::StringParseError)?;
-            let direction = Direction::from_char(direction)?;
-            let distance = parts.next().ok_or(InputParseError::StringParseError)?;
-            let distance = distance.parse().map_err(InputParseError::ParseIntError)?;
-            let color = parts.next().map(|s| s[2..s.len() - 1].to_string());
-            Ok(Dig { direction, distance, color })
+            Dig::from_str(line).map_err(|err| err.within(s, line))
         }).collect();
         Ok(PuzzleInput { edges: digs? })
     }
 }
 
-pub enum InputParseError {
-    StringParseError,
-    ParseIntError(std::num::ParseIntError),
+impl FromStr for Dig {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Dig, Self::Err> {
+        let mut parts = s.split(' ');
+        let direction = parts.next().unwrap_or(s);
+        let direction = parse_at(s, direction, Direction::EXPECTED)?;
+        let distance = parts.next().ok_or_else(|| ParseError::end_of(s, "a distance"))?;
+        let distance = parse_at(s, distance, "a distance")?;
+        let color = parts.next().map(|part| {
+            part.strip_prefix("(#")
+                .and_then(|color| color.strip_suffix(')'))
+                .filter(|color| color.len() == 6 && color.chars().all(|c| c.is_ascii_hexdigit()))
+                .map(str::to_string)
+                .ok_or_else(|| ParseError::at(s, part, "a color `(#rrggbb)`"))
+        }).transpose()?;
+        Ok(Dig { direction, distance, color })
+    }
 }
 
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 struct Dig {
     direction: Direction,
@@ -190,62 +182,44 @@ struct Dig {
     color: Option<String>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
 
-impl Direction {
-    fn from_char(c: char) -> Result<Direction, InputParseError> {
-        match c {
-            'U' => Ok(Direction::Up),
-            'D' => Ok(Direction::Down),
-            'L' => Ok(Direction::Left),
-            'R' => Ok(Direction::Right),
-            _ => Err(InputParseError::StringParseError),
-        }
-    }
+#[cfg(test)]
+mod tests {
+    use aoc::aux::{INPUT_FILE, TEST_INPUT_FILE};
+    use aoc::puzzle::{read_and_solve_part1, read_and_solve_part2};
+    use super::*;
 
-    fn dy(&self) -> isize {
-        match self {
-            Direction::Up => -1,
-            Direction::Down => 1,
-            _ => 0,
-        }
+    const TEST_SOLUTION_1: Solution1 = 62;
+    const TEST_SOLUTION_2: Solution2 = 952408144115;
+
+    #[test]
+    fn test_part1() {
+        let test_output = read_and_solve_part1::<Day18>(TEST_INPUT_FILE);
+        assert_eq!(test_output, TEST_SOLUTION_1);
     }
 
-    fn dx(&self) -> isize {
-        match self {
-            Direction::Left => -1,
-            Direction::Right => 1,
-            _ => 0,
-        }
+    #[test]
+    fn test_parse_error() {
+        let err = "R 6 (#70c710)\nD 5 (#0dc571)\nX 2 (#5713f0)".parse::<PuzzleInput>().unwrap_err();
+        assert_eq!(err, ParseError::new(3, 1, "X", Direction::EXPECTED));
+
+        let err = "R 6 (#70c710)\nD 5 (#0dc5z1)".parse::<PuzzleInput>().unwrap_err();
+        assert_eq!(err, ParseError::new(2, 5, "(#0dc5z1)", "a color `(#rrggbb)`"));
     }
-}
 
+    #[test]
+    fn test_part2() {
+        let test_output = read_and_solve_part2::<Day18>(TEST_INPUT_FILE);
+        assert_eq!(test_output, TEST_SOLUTION_2);
+    }
 
-/////////////////////////////////////////////////////////////////////////////
-// Pretty-printing
-/////////////////////////////////////////////////////////////////////////////
-impl fmt::Display for Direction {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Direction::Up => write!(f, "U"),
-            Direction::Down => write!(f, "D"),
-            Direction::Left => write!(f, "L"),
-            Direction::Right => write!(f, "R"),
-        }
+    #[bench]
+    fn bench_part1(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part1::<Day18>(INPUT_FILE));
     }
-}
 
-impl fmt::Display for InputParseError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            InputParseError::StringParseError => write!(f, "Error parsing string"),
-            InputParseError::ParseIntError(err) => write!(f, "Error parsing integer: {}", err),
-        }
+    #[bench]
+    fn bench_part2(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part2::<Day18>(INPUT_FILE));
     }
 }
@@ -1,13 +1,37 @@
-use std::cmp::{max, min};
 use std::collections::HashMap;
 use std::ops::RangeInclusive;
 use std::str::FromStr;
 
+use aoc::aux::intervals::{BoxSet, Cuboid, IntervalSet};
+use aoc::error::{parse_at, ParseError};
+use aoc::puzzle::Puzzle;
+
 
 pub type Solution1 = u64;
 pub type Solution2 = u64;
 
-const TERMINAL_STATES: [&str; 2] = ["A", "R"];
+
+pub struct Day19;
+
+impl Puzzle for Day19 {
+    type Input = PuzzleInput;
+    type Output1 = Solution1;
+    type Output2 = Solution2;
+    type ParseError = <PuzzleInput as FromStr>::Err;
+
+    fn parse(input: &str) -> Result<PuzzleInput, Self::ParseError> {
+        input.parse()
+    }
+
+    fn part1(input: &PuzzleInput) -> Solution1 {
+        solve_part1(input)
+    }
+
+    fn part2(input: &PuzzleInput) -> Solution2 {
+        solve_part2(input)
+    }
+}
+
 const RATING_RANGE: RangeInclusive<u32> = 1..=4000;
 
 
@@ -23,15 +47,9 @@ pub fn solve_part1(input: &PuzzleInput) -> Solution1 {
 }
 
 pub fn solve_part2(input: &PuzzleInput) -> Solution2 {
-    let starting_rect = Rectangle {
-        x: RATING_RANGE.clone(),
-        m: RATING_RANGE.clone(),
-        a: RATING_RANGE.clone(),
-        s: RATING_RANGE.clone()
-    };
-    let starting_union = RectangleUnion { rectangles: vec![starting_rect] };
-    let final_union = traverse_union(&starting_union, &input.first, &input.workflows);
-    final_union.area() as Solution2
+    let all_ratings = Cuboid::new([(); 4].map(|_| IntervalSet::from_inclusive(RATING_RANGE)));
+    let final_union = traverse_union(&BoxSet::from(all_ratings), &input.first, &input.workflows);
+    final_union.volume()
 }
 
 fn traverse_single(rating: &Rating, start: &str, workflows: &HashMap<String, Workflow>) -> bool {
@@ -58,144 +76,42 @@ fn traverse_single(rating: &Rating, start: &str, workflows: &HashMap<String, Wor
     }
 }
 
-fn traverse_union(union: &RectangleUnion, start: &str, workflows: &HashMap<String, Workflow>) -> RectangleUnion {
-    let mut state: HashMap<&str, RectangleUnion> = [(start, union.clone())].into();
-    while state.keys().any(|id| !TERMINAL_STATES.contains(id)) {
-        let mut new_state = HashMap::new();
+/// The set of ratings, as points in `xmas` space
+type Region = BoxSet<u32, 4>;
+
+fn traverse_union(union: &Region, start: &str, workflows: &HashMap<String, Workflow>) -> Region {
+    let mut state: HashMap<&str, Region> = [(start, union.clone())].into();
+    let mut accepted = Region::new();
+    while !state.is_empty() {
+        let mut new_state: HashMap<&str, Region> = HashMap::new();
         for (id, region) in state.iter() {
-            if TERMINAL_STATES.contains(id) {
-                continue;
-            }
             let workflow = workflows.get(&id.to_string()).unwrap();
             let mut remaining_region = region.clone();
+            let mut targets = Vec::new();
             'rules: for rule in workflow.rules.iter() {
-                let (mapped_region, remainder) = remaining_region.split(&rule.condition);
+                let (mapped_region, remainder) = remaining_region.partition(rule.condition.attribute().axis(), &rule.condition.accepted_values());
                 remaining_region = remainder;
-                if !mapped_region.is_empty() {
-                    let next_region = new_state.entry(rule.direction.as_ref()).or_insert_with(RectangleUnion::new);
-                    next_region.rectangles.extend(mapped_region.rectangles);
-                }
+                targets.push((rule.direction.as_str(), mapped_region));
                 if remaining_region.is_empty() {
                     break 'rules;
                 }
             }
-            if !remaining_region.is_empty() {
-                let next_region = new_state.entry(workflow.default.as_ref()).or_insert_with(RectangleUnion::new);
-                next_region.rectangles.extend(remaining_region.rectangles);
+            targets.push((workflow.default.as_str(), remaining_region));
+            for (target, region) in targets.into_iter().filter(|(_, region)| !region.is_empty()) {
+                match target {
+                    "A" => accepted.extend(region),
+                    "R" => {},
+                    _ => {
+                        new_state.entry(target).or_default().extend(region);
+                    }
+                }
             }
         }
-        if let Some(accepted_region) = state.get("A") {
-            let new_accepted_region = new_state.entry("A").or_insert_with(RectangleUnion::new);
-            new_accepted_region.rectangles.extend(accepted_region.rectangles.iter().cloned());
-        }
         state = new_state;
     }
-    state["A"].clone()
-}
-
-#[derive(Clone, Debug)]
-struct RectangleUnion {
-    rectangles: Vec<Rectangle>
-}
-
-impl RectangleUnion {
-    fn new() -> Self {
-        RectangleUnion { rectangles: Vec::new() }
-    }
-
-    fn is_empty(&self) -> bool {
-        self.rectangles.iter().all(|rect| rect.is_empty())
-    }
-
-    fn split(&self, condition: &Condition) -> (RectangleUnion, RectangleUnion) {
-        let mut matched = RectangleUnion::new();
-        let mut remaining = RectangleUnion::new();
-        for rect in self.rectangles.iter() {
-            let (matching_rect, remaining_rect) = rect.split(condition);
-            if !matching_rect.is_empty() {
-                matched.rectangles.push(matching_rect);
-            }
-            if !remaining_rect.is_empty() {
-                remaining.rectangles.push(remaining_rect);
-            }
-        }
-        (matched, remaining)
-    }
-
-    fn area(&self) -> u64 {
-        self.rectangles.iter().map(Rectangle::area).sum()
-    }
+    accepted
 }
 
-#[derive(Clone, Debug)]
-struct Rectangle {
-    x: RangeInclusive<u32>,
-    m: RangeInclusive<u32>,
-    a: RangeInclusive<u32>,
-    s: RangeInclusive<u32>
-}
-
-impl Rectangle {
-    fn is_empty(&self) -> bool {
-        self.x.is_empty() || self.m.is_empty() || self.a.is_empty() || self.s.is_empty()
-    }
-
-    fn area(&self) -> u64 {
-        if self.is_empty() { 0 } else {
-            (self.x.end() - self.x.start() + 1) as u64
-            * (self.m.end() - self.m.start() + 1) as u64
-            * (self.a.end() - self.a.start() + 1) as u64
-            * (self.s.end() - self.s.start() + 1) as u64
-        }
-    }
-
-    fn split(&self, condition: &Condition) -> (Rectangle, Rectangle) {
-        match condition {
-            Condition::GreaterThan(Attribute::X, value) => {
-                let matching = Rectangle { x: max(value + 1, *self.x.start())..=*self.x.end(), ..self.clone() };
-                let remaining = Rectangle { x: *self.x.start()..=min(*value, *self.x.end()), ..self.clone() };
-                (matching, remaining)
-            }
-            Condition::GreaterThan(Attribute::M, value) => {
-                let matching = Rectangle { m: max(value + 1, *self.m.start())..=*self.m.end(), ..self.clone() };
-                let remaining = Rectangle { m: *self.m.start()..=min(*value, *self.m.end()), ..self.clone() };
-                (matching, remaining)
-            }
-            Condition::GreaterThan(Attribute::A, value) => {
-                let matching = Rectangle { a: max(value + 1, *self.a.start())..=*self.a.end(), ..self.clone() };
-                let remaining = Rectangle { a: *self.a.start()..=min(*value, *self.a.end()), ..self.clone() };
-                (matching, remaining)
-            }
-            Condition::GreaterThan(Attribute::S, value) => {
-                let matching = Rectangle { s: max(value + 1, *self.s.start())..=*self.s.end(), ..self.clone() };
-                let remaining = Rectangle { s: *self.s.start()..=min(*value, *self.s.end()), ..self.clone() };
-                (matching, remaining)
-            }
-            Condition::LessThan(Attribute::X, value) => {
-                let matching = Rectangle { x: *self.x.start()..=min(value - 1, *self.x.end()), ..self.clone() };
-                let remaining = Rectangle { x: max(*value, *self.x.start())..=*self.x.end(), ..self.clone() };
-                (matching, remaining)
-            }
-            Condition::LessThan(Attribute::M, value) => {
-                let matching = Rectangle { m: *self.m.start()..=min(value - 1, *self.m.end()), ..self.clone() };
-                let remaining = Rectangle { m: max(*value, *self.m.start())..=*self.m.end(), ..self.clone() };
-                (matching, remaining)
-            }
-            Condition::LessThan(Attribute::A, value) => {
-                let matching = Rectangle { a: *self.a.start()..=min(value - 1, *self.a.end()), ..self.clone() };
-                let remaining = Rectangle { a: max(*value, *self.a.start())..=*self.a.end(), ..self.clone() };
-                (matching, remaining)
-            }
-            Condition::LessThan(Attribute::S, value) => {
-                let matching = Rectangle { s: *self.s.start()..=min(value - 1, *self.s.end()), ..self.clone() };
-                let remaining = Rectangle { s: max(*value, *self.s.start())..=*self.s.end(), ..self.clone() };
-                (matching, remaining)
-            }
-        }
-    }
-}
-
-
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PuzzleInput {
     workflows: HashMap<String, Workflow>,
@@ -247,6 +163,32 @@ impl Condition {
             Condition::LessThan(Attribute::S, value) => rating.s < *value,
         }
     }
+
+    fn attribute(&self) -> &Attribute {
+        match self {
+            Condition::GreaterThan(attribute, _) | Condition::LessThan(attribute, _) => attribute,
+        }
+    }
+
+    /// The values of the attribute that match the condition
+    fn accepted_values(&self) -> IntervalSet<u32> {
+        match self {
+            Condition::GreaterThan(_, value) => IntervalSet::from_inclusive(value + 1..=*RATING_RANGE.end()),
+            Condition::LessThan(_, value) => IntervalSet::from(*RATING_RANGE.start()..*value),
+        }
+    }
+}
+
+impl Attribute {
+    /// The axis of this attribute in `xmas` space
+    fn axis(&self) -> usize {
+        match self {
+            Attribute::X => 0,
+            Attribute::M => 1,
+            Attribute::A => 2,
+            Attribute::S => 3,
+        }
+    }
 }
 
 impl Rating {
@@ -256,22 +198,23 @@ impl Rating {
 }
 
 impl FromStr for PuzzleInput {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<PuzzleInput, Self::Err> {
         let mut parts = s.split("\n\n");
-        let workflows_str = parts.next().ok_or("Missing workflows")?;
+        let workflows_str = parts.next().unwrap_or(s);
         let mut workflows = HashMap::new();
         for line in workflows_str.lines() {
-            let mut parts = line.split("{");
-            let name = parts.next().ok_or("Missing name")?;
-            let rules_str = parts.next().ok_or("Missing rules")?;
-            let rules_str = rules_str.strip_suffix("}").ok_or("Missing closing brace")?;
-            let workflow: Workflow = rules_str.parse()?;
+            let (name, rules_str) = line.split_once('{').ok_or_else(|| ParseError::at(s, line, "`name{rules}`"))?;
+            let rules_str = rules_str.strip_suffix('}')
+                .ok_or_else(|| ParseError::end_of(line, "`}`").within(s, line))?;
+            let workflow: Workflow = rules_str.parse().map_err(|err: ParseError| err.within(s, rules_str))?;
             workflows.insert(name.to_string(), workflow);
         }
-        let ratings_str = parts.next().ok_or("Missing ratings")?;
-        let ratings: Result<Vec<Rating>, _> = ratings_str.lines().map(|line| line.parse()).collect();
+        let ratings_str = parts.next().ok_or_else(|| ParseError::end_of(s, "an empty line followed by ratings"))?;
+        let ratings: Result<Vec<Rating>, _> = ratings_str.lines()
+            .map(|line| line.parse().map_err(|err: ParseError| err.within(s, line)))
+            .collect();
         let ratings = ratings?;
         let first = "in".to_string();
         Ok(PuzzleInput { workflows, first, ratings })
@@ -279,13 +222,13 @@ impl FromStr for PuzzleInput {
 }
 
 impl FromStr for Workflow {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Workflow, Self::Err> {
-        let rules_parts: Vec<_> = s.split(",").collect();
+        let rules_parts: Vec<_> = s.split(',').collect();
         let default = rules_parts[rules_parts.len() - 1].to_string();
         let rules: Result<Vec<Rule>, _> = rules_parts[..rules_parts.len() - 1].iter()
-            .map(|rule| rule.parse())
+            .map(|rule| rule.parse().map_err(|err: ParseError| err.within(s, rule)))
             .collect();
         let rules = rules?;
         Ok(Workflow { rules, default })
@@ -293,24 +236,23 @@ impl FromStr for Workflow {
 }
 
 impl FromStr for Rule {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Rule, Self::Err> {
-        let mut parts = s.split(":");
-        let condition: Condition = parts.next().ok_or("Missing attribute")?.parse()?;
-        let direction = parts.next().ok_or("Missing direction")?;
+        let (condition, direction) = s.split_once(':').ok_or_else(|| ParseError::at(s, s, "`condition:target`"))?;
+        let condition: Condition = condition.parse().map_err(|err: ParseError| err.within(s, condition))?;
         Ok(Rule { condition, direction: direction.to_string() })
     }
 }
 
 impl FromStr for Condition {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Condition, Self::Err> {
         let symbol = if s.contains('<') {'<'} else {'>'};
-        let mut parts = s.split(symbol);
-        let attribute: Attribute = parts.next().ok_or("Missing attribute")?.parse()?;
-        let value: u32 = parts.next().ok_or("Missing value")?.parse().map_err(|e| format!("Invalid value: {}", e))?;
+        let (attribute, value) = s.split_once(symbol).ok_or_else(|| ParseError::at(s, s, "a comparison with `<` or `>`"))?;
+        let attribute: Attribute = parse_at(s, attribute, "one of `xmas`")?;
+        let value: u32 = parse_at(s, value, "a value")?;
         match symbol {
             '<' => Ok(Condition::LessThan(attribute, value)),
             '>' => Ok(Condition::GreaterThan(attribute, value)),
@@ -320,7 +262,7 @@ impl FromStr for Condition {
 }
 
 impl FromStr for Attribute {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Attribute, Self::Err> {
         match s {
@@ -328,22 +270,71 @@ impl FromStr for Attribute {
             "m" => Ok(Attribute::M),
             "a" => Ok(Attribute::A),
             "s" => Ok(Attribute::S),
-            _ => Err(format!("Invalid attribute: {}", s))
+            _ => Err(ParseError::at(s, s, "one of `xmas`"))
         }
     }
 }
 
 impl FromStr for Rating {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Rating, Self::Err> {
-        let s = s.strip_prefix("{").ok_or("Missing opening brace")?;
-        let s = s.strip_suffix("}").ok_or("Missing closing brace")?;
-        let mut parts = s.split(",");
-        let x: u32 = parts.next().ok_or("Missing x")?[2..].parse().map_err(|e| format!("Invalid x: {}", e))?;
-        let m: u32 = parts.next().ok_or("Missing m")?[2..].parse().map_err(|e| format!("Invalid m: {}", e))?;
-        let a: u32 = parts.next().ok_or("Missing a")?[2..].parse().map_err(|e| format!("Invalid a: {}", e))?;
-        let s: u32 = parts.next().ok_or("Missing s")?[2..].parse().map_err(|e| format!("Invalid s: {}", e))?;
+        let inner = s.strip_prefix('{').ok_or_else(|| ParseError::at(s, s, "`{`"))?;
+        let inner = inner.strip_suffix('}').ok_or_else(|| ParseError::end_of(s, "`}`"))?;
+        let mut parts = inner.split(',');
+        let mut parse_value = |prefix: &str| {
+            let part = parts.next().ok_or_else(|| ParseError::end_of(inner, format!("`,{}`", prefix)))?;
+            let value = part.strip_prefix(prefix).ok_or_else(|| ParseError::at(s, part, format!("`{}`", prefix)))?;
+            parse_at(s, value, "a rating")
+        };
+        let x: u32 = parse_value("x=")?;
+        let m: u32 = parse_value("m=")?;
+        let a: u32 = parse_value("a=")?;
+        let s: u32 = parse_value("s=")?;
         Ok(Rating { x, m, a, s })
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::{INPUT_FILE, TEST_INPUT_FILE};
+    use aoc::puzzle::{read_and_solve_part1, read_and_solve_part2};
+    use super::*;
+
+    const TEST_SOLUTION_1: Solution1 = 19114;
+    const TEST_SOLUTION_2: Solution2 = 167409079868000;
+
+    #[test]
+    fn test_part1() {
+        let test_output = read_and_solve_part1::<Day19>(TEST_INPUT_FILE);
+        assert_eq!(test_output, TEST_SOLUTION_1);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "in{s<1351:px,qqz}\npx{a<2006:qkq,y>2090:A,rfg}\n\n{x=787,m=2655,a=1222,s=2876}\n";
+        let err = input.parse::<PuzzleInput>().unwrap_err();
+        assert_eq!(err, ParseError::new(2, 15, "y", "one of `xmas`"));
+
+        let input = "in{s<1351:px,qqz}\n\n{x=787,m=2655,a=1222,s=2876}\n{x=1679,m=44,a=2067,s=49x}\n";
+        let err = input.parse::<PuzzleInput>().unwrap_err();
+        assert_eq!(err, ParseError::new(4, 23, "49x", "a rating"));
+    }
+
+    #[test]
+    fn test_part2() {
+        let test_output = read_and_solve_part2::<Day19>(TEST_INPUT_FILE);
+        assert_eq!(test_output, TEST_SOLUTION_2);
+    }
+
+    #[bench]
+    fn bench_part1(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part1::<Day19>(INPUT_FILE));
+    }
+
+    #[bench]
+    fn bench_part2(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part2::<Day19>(INPUT_FILE));
+    }
+}
@@ -1,21 +1,43 @@
+#![cfg_attr(test, feature(test))]
+
+#[cfg(test)]
+extern crate test;
+
 use std::collections::HashSet;
-use std::fs;
-use std::io::{self, BufRead};
-use std::path::Path;
+
 use itertools::Itertools;
 
+use aoc::aux::parse_numbers;
+use aoc::error::ParseError;
+use aoc::puzzle::Puzzle;
 
-pub const INPUT_FILE: &str = "input.txt";
+pub mod part1;
+pub mod part2;
 
-pub fn iter_input_lines(filename: impl AsRef<Path>) -> io::Result<impl Iterator<Item = io::Result<String>>> {
-    let file = fs::File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
+pub use part1::solve_part1;
+pub use part2::solve_part2;
 
-pub fn write_output(output: impl ToString) {
-    println!("{}", output.to_string());
-}
 
+pub struct Day4;
+
+impl Puzzle for Day4 {
+    type Input = Box<[Card]>;
+    type Output1 = u64;
+    type Output2 = u64;
+    type ParseError = ParseError;
+
+    fn parse(input: &str) -> Result<Box<[Card]>, Self::ParseError> {
+        parse_input_iter(input.lines().map(String::from))
+    }
+
+    fn part1(input: &Box<[Card]>) -> u64 {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Box<[Card]>) -> u64 {
+        solve_part2(input)
+    }
+}
 
 
 #[derive(Debug)]
@@ -34,26 +56,41 @@ impl Card {
     pub fn score(&self) -> u64 {
         match self.overlap() {
             0 => 0,
-            n => (2 as u64).pow(n - 1)
+            n => 2_u64.pow(n - 1)
         }
     }
 }
 
-pub fn parse_input_iter(input: impl Iterator<Item = String>) -> Box<[Card]> {
-    let cars = input
-        .map(|x| parse_line(&x))
-        .collect();
-    cars
+pub fn parse_input_iter(input: impl Iterator<Item = String>) -> Result<Box<[Card]>, ParseError> {
+    input
+        .enumerate()
+        .map(|(i, x)| parse_line(&x).map_err(|err| err.with_line_offset(i)))
+        .collect()
 }
 
-pub fn parse_line(line: &str) -> Card {
-    let (_prefix, numbers) = line.split(": ").collect_tuple().expect("Invalid input");
-    let (winning, own) = numbers.split(" | ").collect_tuple().expect("Invalid input");
-    let winning_numbers = parse_numbers(winning);
-    let own_numbers = parse_numbers(own);
-    Card { winning_numbers, own_numbers }
+pub fn parse_line(line: &str) -> Result<Card, ParseError> {
+    let (_prefix, numbers) = line.split(": ").collect_tuple()
+        .ok_or_else(|| ParseError::at(line, line, "`Card <id>: <numbers> | <numbers>`"))?;
+    let (winning, own) = numbers.split(" | ").collect_tuple()
+        .ok_or_else(|| ParseError::at(line, numbers, "`<numbers> | <numbers>`"))?;
+    let winning_numbers = parse_numbers(winning).map_err(|err| err.within(line, winning))?;
+    let own_numbers = parse_numbers(own).map_err(|err| err.within(line, own))?;
+    Ok(Card { winning_numbers, own_numbers })
 }
 
-pub fn parse_numbers(numbers: &str) -> Vec<i32> {
-    numbers.split_whitespace().map(|x| x.parse().expect("Invalid input")).collect()
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error() {
+        let input = "Card 1: 41 48 | 83 86\nCard 2: 13 3x | 61 30\nCard 3 1 21 | 69 82";
+        let err = Day4::parse(input).unwrap_err();
+        assert_eq!(err, ParseError::new(2, 12, "3x", "a number"));
+
+        let input = "Card 1: 41 48 | 83 86\nCard 2: 13 32 | 61 30\nCard 3 1 21 | 69 82";
+        let err = Day4::parse(input).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+    }
 }
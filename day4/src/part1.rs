@@ -0,0 +1,26 @@
+use crate::Card;
+
+
+pub fn solve_part1(cards: &[Card]) -> u64 {
+    cards.iter().map(Card::score).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::INPUT_FILE;
+    use aoc::puzzle::read_and_solve_part1;
+    use crate::Day4;
+
+    const TEST_SOLUTION: u64 = 13;
+
+    #[test]
+    fn test_part1() {
+        let test_output = read_and_solve_part1::<Day4>("test_input.txt");
+        assert_eq!(test_output, TEST_SOLUTION);
+    }
+
+    #[bench]
+    fn bench_part1(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part1::<Day4>(INPUT_FILE));
+    }
+}
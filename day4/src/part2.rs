@@ -0,0 +1,35 @@
+use crate::Card;
+
+
+pub fn solve_part2(cards: &[Card]) -> u64 {
+    let size = cards.len();
+    let overlaps: Vec<u32> = cards.iter().map(Card::overlap).collect();
+    let mut n_copies = vec![1; size];
+    for (i, &n) in overlaps.iter().enumerate() {
+        for j in 1..n+1 {
+            n_copies[i + (j as usize)] += n_copies[i];
+        }
+    }
+    n_copies.iter().sum()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::INPUT_FILE;
+    use aoc::puzzle::read_and_solve_part2;
+    use crate::Day4;
+
+    const TEST_SOLUTION: u64 = 30;
+
+    #[test]
+    fn test_part2() {
+        let test_output = read_and_solve_part2::<Day4>("test_input.txt");
+        assert_eq!(test_output, TEST_SOLUTION);
+    }
+
+    #[bench]
+    fn bench_part2(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part2::<Day4>(INPUT_FILE));
+    }
+}
@@ -1,20 +1,40 @@
+#![cfg_attr(test, feature(test))]
+
+#[cfg(test)]
+extern crate test;
+
 use itertools::Itertools;
-use std::fs;
-use std::io::{self, BufRead};
-use std::path::Path;
-use std::str::FromStr;
-
-pub const INPUT_FILE: &str = "input.txt";
-
-pub fn iter_input_lines(
-    filename: impl AsRef<Path>,
-) -> io::Result<impl Iterator<Item = io::Result<String>>> {
-    let file = fs::File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
 
-pub fn write_output(output: impl ToString) {
-    println!("{}", output.to_string());
+use aoc::aux::parse_numbers;
+use aoc::error::ParseError;
+use aoc::puzzle::Puzzle;
+
+pub mod part1;
+pub mod part2;
+
+pub use part1::solve_part1;
+pub use part2::solve_part2;
+
+
+pub struct Day5;
+
+impl Puzzle for Day5 {
+    type Input = PuzzleInput;
+    type Output1 = u64;
+    type Output2 = u64;
+    type ParseError = ParseError;
+
+    fn parse(input: &str) -> Result<PuzzleInput, Self::ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &PuzzleInput) -> u64 {
+        solve_part1(input)
+    }
+
+    fn part2(input: &PuzzleInput) -> u64 {
+        solve_part2(input)
+    }
 }
 
 #[derive(Debug)]
@@ -46,76 +66,53 @@ pub struct Direction {
     pub range: u64,
 }
 
-pub fn parse_input_iter<I>(input: I) -> PuzzleInput
-where
-    I: Iterator<Item = String>,
-{
-    let batches: Vec<Vec<String>> = input
-        .batching(|it| {
-            let mut lines: Vec<String> = Vec::new();
-            for line in it {
-                if line.is_empty() {
-                    break;
-                }
-                lines.push(line);
-            }
-            match lines.is_empty() {
-                true => None,
-                false => Some(lines),
-            }
-        })
-        .collect();
+pub fn parse_input(input: &str) -> Result<PuzzleInput, ParseError> {
+    let mut blocks = input.split("\n\n").filter(|block| !block.trim().is_empty());
 
-    let seeds_str = &batches[0][0];
-    let (_prefix, numbers_str) = seeds_str.split(": ").collect_tuple().unwrap();
-    let seeds = parse_numbers(numbers_str).unwrap();
+    let seeds_str = blocks.next().ok_or_else(|| ParseError::end_of(input, "`seeds: <numbers>`"))?;
+    let (_prefix, numbers_str) = seeds_str.split(": ").collect_tuple()
+        .ok_or_else(|| ParseError::at(input, seeds_str, "`seeds: <numbers>`"))?;
+    let seeds = parse_numbers(numbers_str).map_err(|err| err.within(input, numbers_str))?;
 
-    let maps = batches[1..]
-        .iter()
-        .map(parse_map)
-        .collect();
-    PuzzleInput { seeds, maps }
+    let maps = blocks
+        .map(|block| parse_map(block).map_err(|err| err.within(input, block)))
+        .collect::<Result<_, _>>()?;
+    Ok(PuzzleInput { seeds, maps })
 }
 
-pub fn parse_map(batch: &Vec<String>) -> Map {
-    let directions = batch[1..]
-        .iter()
+pub fn parse_map(block: &str) -> Result<Map, ParseError> {
+    let directions = block
+        .lines()
+        .skip(1)
         .map(|line| {
-            let numbers: Vec<u64> = parse_numbers(line).unwrap();
+            let numbers: Vec<u64> = parse_numbers(line).map_err(|err| err.within(block, line))?;
             let (destination_start, source_start, range) = numbers
                 .into_iter()
                 .collect_tuple()
-                .unwrap();
-            Direction {
+                .ok_or_else(|| ParseError::at(block, line, "three numbers"))?;
+            Ok(Direction {
                 source_start,
                 destination_start,
                 range,
-            }
+            })
         })
-        .collect();
-    Map { directions }
+        .collect::<Result<_, _>>()?;
+    Ok(Map { directions })
 }
 
-/// Parse a whitespace-separated list of numbers
-/// 
-/// # Arguments
-/// 
-/// * `numbers` - A string containing a whitespace-separated list of numbers
-/// 
-/// # Returns
-/// 
-/// A vector of parsed numbers
-/// 
-/// # Example
-/// 
-/// ```
-/// let numbers = "1 2 3 4 5";
-/// let parsed = day5::parse_numbers(numbers);
-/// assert_eq!(parsed, Ok(vec![1, 2, 3, 4, 5]));
-/// ```
-pub fn parse_numbers<T: FromStr>(numbers: &str) -> Result<Vec<T>, <T as FromStr>::Err> {
-    numbers
-        .split_whitespace()
-        .map(|x| x.parse())
-        .collect()
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error() {
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\nsoil-to-fertilizer map:\n0 15 37\n37 52 -2\n";
+        let err = parse_input(input).unwrap_err();
+        assert_eq!(err, ParseError::new(9, 7, "-2", "a number"));
+
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50\n";
+        let err = parse_input(input).unwrap_err();
+        assert_eq!(err, ParseError::new(5, 1, "52 50", "three numbers"));
+    }
 }
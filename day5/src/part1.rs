@@ -0,0 +1,29 @@
+use crate::PuzzleInput;
+
+
+pub fn solve_part1(input: &PuzzleInput) -> u64 {
+    let locations = input.seeds.iter().map(|&seed| {
+        input.maps.iter().fold(seed, |location, map| map.apply(location))
+    });
+    locations.min().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::INPUT_FILE;
+    use aoc::puzzle::read_and_solve_part1;
+    use crate::Day5;
+
+    const TEST_SOLUTION: u64 = 35;
+
+    #[test]
+    fn test_part1() {
+        let test_output = read_and_solve_part1::<Day5>("test_input.txt");
+        assert_eq!(test_output, TEST_SOLUTION);
+    }
+
+    #[bench]
+    fn bench_part1(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part1::<Day5>(INPUT_FILE));
+    }
+}
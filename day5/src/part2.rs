@@ -0,0 +1,110 @@
+use std::ops::Range;
+
+use aoc::aux::intervals::IntervalSet;
+
+use crate::{PuzzleInput, Direction};
+
+
+pub fn solve_part2(input: &PuzzleInput) -> u64 {
+    let (seed_ranges, _remainder) = input.seeds.as_chunks();
+    let mut merged_ranges: IntervalSet<u64> = seed_ranges.iter().map(|[start, len]|
+        Range { start: *start, end: *start + *len }
+    )
+    .collect();
+    for map in &input.maps {
+        let mut unmapped = merged_ranges;
+        let mut mapped = IntervalSet::new();
+        for direction in &map.directions {
+            let (remainders, redirected) = redirect(&unmapped, direction);
+            unmapped = remainders;
+            mapped = mapped.union(&redirected);
+        }
+        merged_ranges = mapped.union(&unmapped);
+    }
+    merged_ranges.min().unwrap()
+}
+
+/// Redirect a set of ranges by a direction
+/// 
+/// # Arguments
+/// 
+/// * `ranges` - The ranges to redirect
+/// * `direction` - The direction to redirect by
+/// 
+/// # Returns
+/// 
+/// A tuple of the remainders and the redirected ranges.
+/// The remainders are the part of `ranges` that was not redirected.
+/// The redirected ranges are the part of `ranges` that was redirected, after redirection.
+fn redirect(ranges: &IntervalSet<u64>, direction: &Direction) -> (IntervalSet<u64>, IntervalSet<u64>) {
+    let source_range = IntervalSet::from(direction.source_start..(direction.source_start + direction.range));
+    let remainders = ranges.difference(&source_range);
+    let redirected = ranges.intersection(&source_range).ranges().iter().map(|intersection| {
+        let new_start = (intersection.start - direction.source_start) + direction.destination_start;
+        let new_end = (intersection.end - direction.source_start) + direction.destination_start;
+        new_start..new_end
+    }).collect();
+    (remainders, redirected)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::INPUT_FILE;
+    use aoc::puzzle::read_and_solve_part2;
+    use crate::Day5;
+    use super::*;
+
+    const TEST_SOLUTION: u64 = 46;
+
+    #[test]
+    fn test_part2() {
+        let test_output = read_and_solve_part2::<Day5>("test_input.txt");
+        assert_eq!(test_output, TEST_SOLUTION);
+    }
+
+    #[test]
+    fn test_redirect() {
+        let ranges = [0..3, 5..8].into_iter().collect();
+        let direction = Direction { source_start: 2, destination_start: 10, range: 2 };
+        let (remainders, redirected) = redirect(&ranges, &direction);
+        assert_eq!(remainders.union(&redirected).ranges(), [0..2, 5..8, 10..11]);
+    }
+
+    #[test]
+    fn test_range_union() {
+        let ranges = vec![6..9, 2..5, 0..3, 3..4];
+        let union: IntervalSet<u64> = ranges.into_iter().collect();
+        assert_eq!(union.ranges(), [0..5, 6..9]);
+
+        let ranges: Vec<Range<u64>> = vec![46..50, 54..63, 74..77, 45..56];
+        let union: IntervalSet<u64> = ranges.into_iter().collect();
+        assert_eq!(union.ranges(), [45..63, 74..77]);
+
+        let union: IntervalSet<u64> = [0..3, 2..5, 3..4, 3..5, 6..9].into_iter().collect();
+        assert_eq!(union.ranges(), [0..5, 6..9]);
+    }
+
+    #[test]
+    fn test_range_intersection() {
+        let range = IntervalSet::<u64>::from(0..5);
+        let other = IntervalSet::from(2..7);
+        assert_eq!(range.difference(&other), IntervalSet::from(0..2));
+        assert_eq!(range.intersection(&other), IntervalSet::from(2..5));
+
+        let range = IntervalSet::<u64>::from(5..7);
+        let other = IntervalSet::from(0..4);
+        assert_eq!(range.difference(&other), IntervalSet::from(5..7));
+        assert!(range.intersection(&other).is_empty());
+
+        let range = IntervalSet::<u64>::from(0..5);
+        let other = IntervalSet::from(6..7);
+        assert_eq!(range.difference(&other), IntervalSet::from(0..5));
+        assert!(range.intersection(&other).is_empty());
+    }
+
+    #[bench]
+    fn bench_part2(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part2::<Day5>(INPUT_FILE));
+    }
+}
@@ -0,0 +1,40 @@
+// Print the shape of every ghost walk of an input, and which assumptions behind the shortcut for part 2 hold.
+//
+//     cargo run -p day8 --example analyze [input]
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use aoc::aux::{read_input, INPUT_FILE};
+
+use day8::analysis::{analyze, Assumptions};
+use day8::parse_input;
+
+
+fn main() -> ExitCode {
+    let path = env::args().nth(1).map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join(INPUT_FILE));
+    let input = match read_input(&path) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Error reading {}: {}", path.display(), err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let input = match parse_input(&input) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Error parsing {}: {}", path.display(), err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let reports = analyze(&input);
+    for report in &reports {
+        println!("{}", report);
+    }
+    println!();
+    println!("{}", Assumptions::check(&reports, input.instructions.len()));
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,203 @@
+use std::fmt;
+
+use itertools::Itertools;
+
+use aoc::aux::until_repeat;
+use aoc::aux::number_theory::{gcd, is_prime};
+
+use crate::PuzzleInput;
+
+
+// The shape of every ghost walk, and the structural assumptions under which part 2 is just the product of the cycle lengths.
+// `cargo run -p day8 --example analyze [input]` prints both.
+
+
+/// The steps at which the walk from a single `xxA` node is at a Z-node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WalkReport {
+    /// The name of the start node
+    pub start: String,
+    /// The number of steps before the walk starts repeating, a whole number of passes through the instructions
+    pub preamble_len: u64,
+    /// The number of steps after which the walk repeats, a whole number of passes through the instructions
+    pub cycle_len: u64,
+    /// The steps before `preamble_len` at which the walk is at a Z-node
+    pub preamble_z: Vec<u64>,
+    /// The steps at which the walk is at a Z-node, counted from `preamble_len`, and less than `cycle_len`
+    pub cycle_z: Vec<u64>,
+}
+
+impl WalkReport {
+    fn new(input: &PuzzleInput, start: usize) -> WalkReport {
+        let network = &input.network;
+        let (preamble_passes, pass_ends) = until_repeat(&start, |&node| network.pass(node, &input.instructions));
+        let n_instructions = input.instructions.len();
+        let cycle_passes = pass_ends.len() - preamble_passes;
+
+        // Walk through the preamble and one cycle, recording the steps at which the walk is at a Z-node
+        let z_locs = network.walk(start, &input.instructions)
+            .take((preamble_passes + cycle_passes) * n_instructions)
+            .enumerate()
+            .filter(|&(_, node)| network.name(node).ends_with('Z'))
+            .map(|(step, _)| step as u64);
+        let preamble_len = (preamble_passes * n_instructions) as u64;
+        let (preamble_z, cycle_z): (Vec<u64>, Vec<u64>) = z_locs.partition(|&step| step < preamble_len);
+        WalkReport {
+            start: network.name(start).to_string(),
+            preamble_len,
+            cycle_len: (cycle_passes * n_instructions) as u64,
+            preamble_z,
+            cycle_z: cycle_z.into_iter().map(|step| step - preamble_len).collect(),
+        }
+    }
+
+    /// Whether the walk is at a Z-node after `step` steps.
+    pub fn is_z_at(&self, step: u64) -> bool {
+        if step < self.preamble_len {
+            self.preamble_z.contains(&step)
+        } else {
+            self.cycle_z.contains(&((step - self.preamble_len) % self.cycle_len))
+        }
+    }
+}
+
+impl fmt::Display for WalkReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: preamble of {} steps, Z at {:?}; cycle of {} steps, Z at {:?}",
+               self.start, self.preamble_len, self.preamble_z, self.cycle_len, self.cycle_z)
+    }
+}
+
+/// Analyze the walk from every `xxA` node, in the order in which they are defined.
+pub fn analyze(input: &PuzzleInput) -> Vec<WalkReport> {
+    input.network.ending_with("A")
+        .map(|start| WalkReport::new(input, start))
+        .collect()
+}
+
+
+/// Which of the structural assumptions behind the shortcut for part 2 hold for a set of walks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Assumptions {
+    /// Every walk enters its cycle after a single pass through the instructions
+    pub single_pass_preamble: bool,
+    /// No walk is at a Z-node before it enters its cycle
+    pub no_z_in_preamble: bool,
+    /// Every cycle contains exactly one Z-node
+    pub one_z_per_cycle: bool,
+    /// That Z-node is exactly one pass through the instructions before the end of the cycle
+    pub z_one_pass_before_cycle_end: bool,
+    /// Every cycle is a prime number of passes through the instructions
+    pub prime_passes: bool,
+    /// The numbers of passes of the cycles are pairwise coprime
+    pub coprime_passes: bool,
+}
+
+impl Assumptions {
+    /// Check the assumptions for the walks in `reports`, with `n_instructions` instructions per pass.
+    pub fn check(reports: &[WalkReport], n_instructions: usize) -> Assumptions {
+        let n_instructions = n_instructions as u64;
+        let passes: Vec<u64> = reports.iter().map(|report| report.cycle_len / n_instructions).collect();
+        Assumptions {
+            single_pass_preamble: reports.iter().all(|report| report.preamble_len == n_instructions),
+            no_z_in_preamble: reports.iter().all(|report| report.preamble_z.is_empty()),
+            one_z_per_cycle: reports.iter().all(|report| report.cycle_z.len() == 1),
+            z_one_pass_before_cycle_end: reports.iter()
+                .all(|report| report.cycle_z.iter().all(|&z| z + n_instructions == report.cycle_len)),
+            prime_passes: passes.iter().all(|&p| is_prime(p as i64)),
+            coprime_passes: passes.iter().tuple_combinations().all(|(&p, &q)| gcd(p as i64, q as i64) == 1),
+        }
+    }
+
+    /// Whether the first step at which all walks are at a Z-node is the length of a pass times the product of the numbers of passes.
+    ///
+    /// Under these assumptions, every walk is at a Z-node exactly at the multiples of its cycle length,
+    /// so the answer is the least common multiple of the cycle lengths.
+    pub fn shortcut_applies(&self) -> bool {
+        self.single_pass_preamble && self.no_z_in_preamble && self.one_z_per_cycle
+            && self.z_one_pass_before_cycle_end && self.coprime_passes
+    }
+}
+
+impl fmt::Display for Assumptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let checks = [
+            (self.single_pass_preamble, "every walk enters its cycle after a single pass"),
+            (self.no_z_in_preamble, "no walk is at a Z-node before its cycle"),
+            (self.one_z_per_cycle, "every cycle contains exactly one Z-node"),
+            (self.z_one_pass_before_cycle_end, "every Z-node is one pass before the end of its cycle"),
+            (self.prime_passes, "every cycle is a prime number of passes"),
+            (self.coprime_passes, "the numbers of passes are pairwise coprime"),
+        ];
+        for (holds, description) in checks {
+            writeln!(f, "{} {}", if holds { '✓' } else { '✗' }, description)?;
+        }
+        write!(f, "The shortcut {}", if self.shortcut_applies() { "applies" } else { "does not apply" })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::{read_input, INPUT_FILE};
+    use crate::parse_input;
+    use super::*;
+
+    #[test]
+    fn test_report() {
+        let input = parse_input("LR\n\n11A = (11Z, XXX)\n11Z = (11B, 11B)\n11B = (11Z, 11Z)\nXXX = (XXX, XXX)\n").unwrap();
+        let reports = analyze(&input);
+        assert_eq!(reports, [WalkReport { start: "11A".to_string(), preamble_len: 2, cycle_len: 2, preamble_z: vec![1], cycle_z: vec![1] }]);
+        assert!((0..10).all(|step| reports[0].is_z_at(step) == (step % 2 == 1)));
+        assert_eq!(reports[0].to_string(), "11A: preamble of 2 steps, Z at [1]; cycle of 2 steps, Z at [1]");
+
+        let assumptions = Assumptions::check(&reports, 2);
+        assert!(!assumptions.no_z_in_preamble && !assumptions.z_one_pass_before_cycle_end && !assumptions.prime_passes);
+        assert!(assumptions.single_pass_preamble && assumptions.one_z_per_cycle && assumptions.coprime_passes);
+        assert!(!assumptions.shortcut_applies());
+    }
+
+    #[test]
+    fn test_assumptions_hold() {
+        let input = parse_input(&read_input(INPUT_FILE).unwrap()).unwrap();
+        let reports = analyze(&input);
+        assert_eq!(reports.len(), 6);
+        let assumptions = Assumptions::check(&reports, input.instructions.len());
+        assert!(assumptions.shortcut_applies());
+        assert!(assumptions.prime_passes);
+        assert!(!assumptions.to_string().contains('✗'));
+
+        let shortcut = reports.iter().map(|report| report.cycle_len / input.instructions.len() as u64).product::<u64>()
+            * input.instructions.len() as u64;
+        assert_eq!(shortcut, crate::solve_part2(&input));
+    }
+
+    #[test]
+    fn test_assumptions_violated() {
+        // 22A is only at a Z-node during its preamble, and both cycles are 2 passes long
+        let input = parse_input("\
+L
+
+11A = (11Z, XXX)
+11Z = (11B, XXX)
+11B = (11Z, XXX)
+22A = (22Z, XXX)
+22Z = (22B, XXX)
+22B = (22C, XXX)
+22C = (22B, XXX)
+XXX = (XXX, XXX)
+").unwrap();
+        let reports = analyze(&input);
+        assert_eq!(reports[1], WalkReport { start: "22A".to_string(), preamble_len: 2, cycle_len: 2, preamble_z: vec![1], cycle_z: vec![] });
+        let assumptions = Assumptions::check(&reports, 1);
+        assert_eq!(assumptions, Assumptions {
+            single_pass_preamble: false,
+            no_z_in_preamble: false,
+            one_z_per_cycle: false,
+            z_one_pass_before_cycle_end: false,
+            prime_passes: true,
+            coprime_passes: false,
+        });
+        assert!(!assumptions.shortcut_applies());
+    }
+}
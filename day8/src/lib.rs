@@ -1,25 +1,51 @@
-use std::fs;
-use std::io;
-use std::path::Path;
-use std::str::FromStr;
+#![cfg_attr(test, feature(test))]
+
+#[cfg(test)]
+extern crate test;
 
 use lazy_static::lazy_static;
 use regex::Regex;
 
-pub const INPUT_FILE: &str = "input.txt";
+use aoc::error::ParseError;
+use aoc::puzzle::Puzzle;
+
+pub mod analysis;
+pub mod lockstep;
+pub mod network;
+pub mod part1;
+pub mod part2;
+
+pub use network::{Network, Node};
+pub use part1::solve_part1;
+pub use part2::solve_part2;
 
-pub fn read_input(path: impl AsRef<Path>) -> io::Result<String> {
-    fs::read_to_string(path)
-}
 
-pub fn write_output(output: impl ToString) {
-    println!("{}", output.to_string());
+pub struct Day8;
+
+impl Puzzle for Day8 {
+    type Input = PuzzleInput;
+    type Output1 = u32;
+    type Output2 = u64;
+    type ParseError = ParseError;
+
+    fn parse(input: &str) -> Result<PuzzleInput, Self::ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &PuzzleInput) -> u32 {
+        solve_part1(input)
+    }
+
+    fn part2(input: &PuzzleInput) -> u64 {
+        solve_part2(input)
+    }
 }
 
+
 #[derive(Clone, Debug)]
 pub struct PuzzleInput {
     pub instructions: Vec<Instruction>,
-    pub map: Vec<(String, String, String)>,
+    pub network: Network,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -27,75 +53,63 @@ pub enum Instruction {
     Left, Right
 }
 
-pub fn parse_input(input: &str) -> PuzzleInput {
+pub fn parse_input(input: &str) -> Result<PuzzleInput, ParseError> {
     lazy_static! {
-        static ref INSTRUCTIONS_REGEX: Regex = Regex::new(r"^([L|R]+)").unwrap();
-        static ref MAP_REGEX: Regex = Regex::new(r"(\w+) = \((\w+), (\w+)\)").unwrap();
+        static ref MAP_REGEX: Regex = Regex::new(r"^(\w+) = \((\w+), (\w+)\)$").unwrap();
     }
-    let Some(captures) = INSTRUCTIONS_REGEX.captures(input) else {
-        panic!("Invalid input")
-    };
-    let instructions = captures[0].chars().map(|c| {
+    let mut lines = input.lines();
+    let first_line = lines.next().unwrap_or(input);
+    let instructions = first_line.char_indices().map(|(i, c)| {
         match c {
-            'L' => Instruction::Left,
-            'R' => Instruction::Right,
-            _ => panic!("Invalid instruction")
+            'L' => Ok(Instruction::Left),
+            'R' => Ok(Instruction::Right),
+            _ => Err(ParseError::at_char(first_line, i, "`L` or `R`").within(input, first_line))
         }
-    }).collect();
+    }).collect::<Result<Vec<_>, _>>()?;
+    if instructions.is_empty() {
+        return Err(ParseError::end_of(first_line, "`L` or `R`").within(input, first_line));
+    }
 
-    let captures = MAP_REGEX.captures_iter(input);
-    let map = captures.map(|capture| {
+    let definitions = lines.filter(|line| !line.is_empty()).map(|line| {
+        let Some(capture) = MAP_REGEX.captures(line) else {
+            return Err(ParseError::at(input, line, "`XXX = (YYY, ZZZ)`"));
+        };
         let (_, [s1, s2, s3]) = capture.extract();
-        (s1.to_owned(), s2.to_owned(), s3.to_owned())
-    }).collect();
+        Ok((s1, s2, s3))
+    }).collect::<Result<Vec<_>, _>>()?;
+    let network = Network::from_definitions(input, &definitions)?;
 
-    PuzzleInput {instructions, map}
+    Ok(PuzzleInput {instructions, network})
 }
 
 
 #[cfg(test)]
 mod tests {
+    use aoc::aux::read_input;
     use super::*;
 
     #[test]
     fn test_parse_input() {
         let test_input = read_input("test_input.txt").unwrap();
-        let parsed = parse_input(&test_input);
+        let parsed = parse_input(&test_input).unwrap();
         assert_eq!(parsed.instructions, vec![Instruction::Right, Instruction::Left]);
-        assert_eq!(parsed.map.len(), 7);
-        assert_eq!(parsed.map[0], (
-            String::from("AAA"),
-            String::from("BBB"),
-            String::from("CCC"),
-        ));
-        assert_eq!(parsed.map[6], (
-            String::from("ZZZ"),
-            String::from("ZZZ"),
-            String::from("ZZZ"),
-        ));
+        let network = &parsed.network;
+        assert_eq!(network.len(), 7);
+        assert_eq!(network.name(0), "AAA");
+        assert_eq!(network.nodes()[0], Node { left: network.find("BBB").unwrap(), right: network.find("CCC").unwrap() });
+        assert_eq!(network.find("ZZZ"), Some(6));
+        assert_eq!(network.nodes()[6], Node { left: 6, right: 6 });
     }
-}
 
-/// Parse a whitespace-separated list of numbers
-/// 
-/// # Arguments
-/// 
-/// * `numbers` - A string containing a whitespace-separated list of numbers
-/// 
-/// # Returns
-/// 
-/// A vector of parsed numbers
-/// 
-/// # Example
-/// 
-/// ```
-/// let numbers = "1 2 3 4 5";
-/// let parsed = day8::parse_numbers(numbers);
-/// assert_eq!(parsed, Ok(vec![1, 2, 3, 4, 5]));
-/// ```
-pub fn parse_numbers<T: FromStr>(numbers: &str) -> Result<Vec<T>, <T as FromStr>::Err> {
-    numbers
-        .split_whitespace()
-        .map(|x| x.parse())
-        .collect()
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("LRX\n\nAAA = (BBB, CCC)\n").unwrap_err();
+        assert_eq!(err, ParseError::new(1, 3, "X", "`L` or `R`"));
+
+        let err = parse_input("LR\n\nAAA = (BBB, CCC)\nBBB = (DDD EEE)\n").unwrap_err();
+        assert_eq!(err, ParseError::new(4, 1, "BBB = (DDD EEE)", "`XXX = (YYY, ZZZ)`"));
+
+        let err = parse_input("LR\n\nAAA = (BBB, CCC)\nBBB = (AAA, AAA)\n").unwrap_err();
+        assert_eq!(err, ParseError::new(3, 13, "CCC", "a defined node"));
+    }
 }
@@ -0,0 +1,218 @@
+use crate::{Instruction, PuzzleInput};
+
+
+// Brute force for part 2: move all walkers in lockstep until they are all at a Z-node.
+// This takes as many steps as the answer, so it is only feasible for small inputs, or to check that there is no
+// simultaneous Z-node below some limit, but it makes no assumptions about the network at all.
+// A full pass through the instructions is precomputed for every node, together with a bitmask of the steps in that
+// pass at which a walk from the node is at a Z-node; a whole pass of all walkers is then a handful of ANDs and lookups.
+
+
+/// The network as a struct of arrays, indexed by node, with everything that a pass through the instructions does precomputed.
+#[derive(Clone, Debug)]
+pub struct NodeTable {
+    left: Vec<u32>,
+    right: Vec<u32>,
+    is_z: Vec<bool>,
+    /// The node reached from each node after a pass through the instructions
+    full_pass: Vec<u32>,
+    /// For each node, `words_per_pass` words with bit `i` set if the walk from the node is at a Z-node after `i` steps of a pass
+    z_masks: Vec<u64>,
+    words_per_pass: usize,
+    n_instructions: usize,
+}
+
+impl NodeTable {
+    pub fn new(input: &PuzzleInput) -> NodeTable {
+        let network = &input.network;
+        let left = network.nodes().iter().map(|node| node.left as u32).collect();
+        let right = network.nodes().iter().map(|node| node.right as u32).collect();
+        let is_z: Vec<bool> = network.names().iter().map(|name| name.ends_with('Z')).collect();
+
+        let n_instructions = input.instructions.len();
+        let words_per_pass = n_instructions.div_ceil(64);
+        let mut full_pass = Vec::with_capacity(network.len());
+        let mut z_masks = vec![0; network.len() * words_per_pass];
+        for start in 0..network.len() {
+            let mut walk = network.walk(start, &input.instructions);
+            for (i, node) in walk.by_ref().take(n_instructions).enumerate() {
+                if is_z[node] {
+                    z_masks[start * words_per_pass + i / 64] |= 1 << (i % 64);
+                }
+            }
+            full_pass.push(walk.next().unwrap() as u32);
+        }
+        NodeTable { left, right, is_z, full_pass, z_masks, words_per_pass, n_instructions }
+    }
+
+    pub fn len(&self) -> usize {
+        self.is_z.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.is_z.is_empty()
+    }
+
+    /// The node reached from `node` after a full pass through the instructions.
+    pub fn full_pass(&self, node: usize) -> usize {
+        self.full_pass[node] as usize
+    }
+
+    fn child(&self, node: u32, dir: &Instruction) -> u32 {
+        match dir {
+            Instruction::Left => self.left[node as usize],
+            Instruction::Right => self.right[node as usize],
+        }
+    }
+
+    fn z_mask(&self, node: u32) -> &[u64] {
+        let start = node as usize * self.words_per_pass;
+        &self.z_masks[start..start + self.words_per_pass]
+    }
+}
+
+
+/// A set of walkers that move through the network in lockstep.
+#[derive(Clone, Debug)]
+pub struct Lockstep<'a> {
+    table: &'a NodeTable,
+    instructions: &'a [Instruction],
+    walkers: Vec<u32>,
+    step: u64,
+}
+
+impl<'a> Lockstep<'a> {
+    /// Start a walker at each of `starts`, where `table` was built from the same `instructions`.
+    pub fn new(table: &'a NodeTable, instructions: &'a [Instruction], starts: impl IntoIterator<Item = usize>) -> Lockstep<'a> {
+        assert_eq!(table.n_instructions, instructions.len(), "The node table was built for different instructions");
+        let walkers = starts.into_iter().map(|start| start as u32).collect();
+        Lockstep { table, instructions, walkers, step: 0 }
+    }
+
+    /// The number of steps taken so far.
+    pub fn step(&self) -> u64 {
+        self.step
+    }
+
+    /// The current node of every walker.
+    pub fn walkers(&self) -> impl Iterator<Item = usize> + '_ {
+        self.walkers.iter().map(|&node| node as usize)
+    }
+
+    pub fn all_at_z(&self) -> bool {
+        self.walkers.iter().all(|&node| self.table.is_z[node as usize])
+    }
+
+    /// Move every walker by a single instruction.
+    pub fn advance(&mut self) {
+        let dir = &self.instructions[(self.step % self.instructions.len() as u64) as usize];
+        for walker in self.walkers.iter_mut() {
+            *walker = self.table.child(*walker, dir);
+        }
+        self.step += 1;
+    }
+
+    /// Move every walker by a full pass through the instructions, which must start at the first instruction.
+    pub fn advance_pass(&mut self) {
+        assert!(self.step.is_multiple_of(self.instructions.len() as u64), "Not at the start of a pass");
+        for walker in self.walkers.iter_mut() {
+            *walker = self.table.full_pass[*walker as usize];
+        }
+        self.step += self.instructions.len() as u64;
+    }
+
+    /// The first step before `step_limit`, from the current one on, at which all walkers are at a Z-node.
+    ///
+    /// On success, the walkers are left at that step; otherwise they are at `step_limit` or just beyond it.
+    pub fn run(&mut self, step_limit: u64) -> Option<u64> {
+        let n_instructions = self.instructions.len() as u64;
+        // Single steps up to the start of a pass, then whole passes
+        while !self.step.is_multiple_of(n_instructions) {
+            if self.step >= step_limit {
+                return None;
+            }
+            if self.all_at_z() {
+                return Some(self.step);
+            }
+            self.advance();
+        }
+        let mut mask = vec![0; self.table.words_per_pass];
+        while self.step < step_limit {
+            mask.fill(u64::MAX);
+            for &walker in &self.walkers {
+                for (word, z_word) in mask.iter_mut().zip(self.table.z_mask(walker)) {
+                    *word &= z_word;
+                }
+            }
+            if let Some((i, word)) = mask.iter().enumerate().find(|(_, &word)| word != 0) {
+                let offset = (i * 64) as u64 + word.trailing_zeros() as u64;
+                if self.step + offset >= step_limit {
+                    return None;
+                }
+                for _ in 0..offset {
+                    self.advance();
+                }
+                return Some(self.step);
+            }
+            self.advance_pass();
+        }
+        None
+    }
+}
+
+
+/// The first step before `step_limit` at which the walks from all `xxA` nodes are at a Z-node, by simulating them all.
+pub fn brute_force(input: &PuzzleInput, step_limit: u64) -> Option<u64> {
+    let table = NodeTable::new(input);
+    Lockstep::new(&table, &input.instructions, input.network.ending_with("A")).run(step_limit)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::{read_input, INPUT_FILE};
+    use crate::parse_input;
+    use super::*;
+
+    #[test]
+    fn test_brute_force() {
+        let input = parse_input(&read_input("test_input2.txt").unwrap()).unwrap();
+        assert_eq!(brute_force(&input, 100), Some(6));
+        assert_eq!(brute_force(&input, 6), None);
+        assert_eq!(brute_force(&input, 7), Some(6));
+    }
+
+    #[test]
+    fn test_against_single_steps() {
+        let input = parse_input(&read_input(INPUT_FILE).unwrap()).unwrap();
+        let table = NodeTable::new(&input);
+        let starts: Vec<usize> = input.network.ending_with("A").collect();
+        let mut single_steps = Lockstep::new(&table, &input.instructions, starts.iter().copied());
+        let mut passes = single_steps.clone();
+        for _ in 0..5 {
+            for _ in 0..input.instructions.len() {
+                single_steps.advance();
+            }
+            passes.advance_pass();
+            assert_eq!(single_steps.walkers().collect::<Vec<_>>(), passes.walkers().collect::<Vec<_>>());
+            assert_eq!(single_steps.step(), passes.step());
+        }
+        for &start in &starts {
+            assert_eq!(table.full_pass(start), input.network.pass(start, &input.instructions));
+        }
+    }
+
+    #[test]
+    fn test_no_early_solution() {
+        // The actual answer is far too big to brute force, but there is no earlier one either
+        let input = parse_input(&read_input(INPUT_FILE).unwrap()).unwrap();
+        assert_eq!(brute_force(&input, 10_000_000), None);
+    }
+
+    #[bench]
+    fn bench_brute_force(b: &mut test::Bencher) {
+        let input = parse_input(&read_input(INPUT_FILE).unwrap()).unwrap();
+        let table = NodeTable::new(&input);
+        b.iter(|| Lockstep::new(&table, &input.instructions, input.network.ending_with("A")).run(100_000_000));
+    }
+}
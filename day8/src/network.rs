@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use std::iter;
+
+use aoc::error::ParseError;
+
+use crate::Instruction;
+
+
+/// The nodes of the map, interned: every node is identified by its index, in the order in which the nodes are defined.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Network {
+    names: Vec<String>,
+    indices: HashMap<String, usize>,
+    nodes: Vec<Node>,
+}
+
+/// The indices of the children of a node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Node {
+    pub left: usize,
+    pub right: usize,
+}
+
+impl Node {
+    pub fn child(&self, dir: &Instruction) -> usize {
+        match dir {
+            Instruction::Left => self.left,
+            Instruction::Right => self.right,
+        }
+    }
+}
+
+impl Network {
+    /// Build a network from `(name, left, right)` definitions, where all names are subslices of `source`,
+    /// failing on a name that is defined twice, or on a child that is never defined.
+    pub fn from_definitions<'a>(source: &str, definitions: &[(&'a str, &'a str, &'a str)]) -> Result<Network, ParseError> {
+        let mut indices = HashMap::new();
+        for (i, &(name, _, _)) in definitions.iter().enumerate() {
+            if indices.insert(name.to_string(), i).is_some() {
+                return Err(ParseError::at(source, name, "a node that is not defined yet"));
+            }
+        }
+        let find = |name: &str| indices.get(name).copied().ok_or_else(|| ParseError::at(source, name, "a defined node"));
+        let nodes = definitions.iter()
+            .map(|&(_, left, right)| Ok(Node { left: find(left)?, right: find(right)? }))
+            .collect::<Result<_, _>>()?;
+        let names = definitions.iter().map(|&(name, _, _)| name.to_string()).collect();
+        Ok(Network { names, indices, nodes })
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The names of all nodes, by index.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// The children of all nodes, by index.
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    pub fn name(&self, node: usize) -> &str {
+        &self.names[node]
+    }
+
+    /// The index of the node called `name`, if there is one.
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.indices.get(name).copied()
+    }
+
+    /// The indices of the nodes whose name ends with `suffix`, such as the `xxA` start nodes.
+    pub fn ending_with<'a>(&'a self, suffix: &'a str) -> impl Iterator<Item = usize> + 'a {
+        (0..self.len()).filter(move |&node| self.names[node].ends_with(suffix))
+    }
+
+    /// The node reached from `node` by following `dir`.
+    pub fn step(&self, node: usize, dir: &Instruction) -> usize {
+        self.nodes[node].child(dir)
+    }
+
+    /// The node reached from `node` by following all of `instructions` once.
+    pub fn pass(&self, node: usize, instructions: &[Instruction]) -> usize {
+        instructions.iter().fold(node, |node, dir| self.step(node, dir))
+    }
+
+    /// The nodes visited from `start` by following `instructions` over and over, starting with `start` itself.
+    ///
+    /// The iterator never ends, unless there are no instructions.
+    pub fn walk<'a>(&'a self, start: usize, instructions: &'a [Instruction]) -> impl Iterator<Item = usize> + 'a {
+        let mut dirs = instructions.iter().cycle();
+        iter::successors(Some(start), move |&node| dirs.next().map(|dir| self.step(node, dir)))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Instruction::*;
+
+    #[test]
+    fn test_walk() {
+        let source = "AAA BBB ZZZ";
+        let (a, b, z) = (&source[0..3], &source[4..7], &source[8..11]);
+        let network = Network::from_definitions(source, &[(a, b, z), (b, a, z), (z, z, z)]).unwrap();
+        assert_eq!(network.find("BBB"), Some(1));
+        assert_eq!(network.find("CCC"), None);
+        assert_eq!(network.name(2), "ZZZ");
+        assert_eq!(network.ending_with("Z").collect::<Vec<_>>(), [2]);
+        assert_eq!(network.walk(0, &[Left, Left, Right]).take(5).collect::<Vec<_>>(), [0, 1, 0, 2, 2]);
+        assert_eq!(network.pass(0, &[Left, Left, Right]), 2);
+        assert_eq!(network.walk(1, &[]).collect::<Vec<_>>(), [1]);
+
+        let err = Network::from_definitions(source, &[(a, b, z), (b, a, a)]).unwrap_err();
+        assert_eq!(err, ParseError::new(1, 9, "ZZZ", "a defined node"));
+        let err = Network::from_definitions(source, &[(a, a, a), (a, a, a)]).unwrap_err();
+        assert_eq!(err, ParseError::new(1, 1, "AAA", "a node that is not defined yet"));
+    }
+}
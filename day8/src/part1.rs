@@ -0,0 +1,30 @@
+use crate::PuzzleInput;
+
+
+pub fn solve_part1(input: &PuzzleInput) -> u32 {
+    let network = &input.network;
+    let start = network.find("AAA").expect("No node AAA");
+    let target = network.find("ZZZ").expect("No node ZZZ");
+    let steps = network.walk(start, &input.instructions).position(|node| node == target).unwrap();
+    steps as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::INPUT_FILE;
+    use aoc::puzzle::read_and_solve_part1;
+    use crate::Day8;
+
+    const TEST_SOLUTION: u32 = 2;
+
+    #[test]
+    fn test_part1() {
+        let test_output = read_and_solve_part1::<Day8>("test_input.txt");
+        assert_eq!(test_output, TEST_SOLUTION);
+    }
+
+    #[bench]
+    fn bench_part1(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part1::<Day8>(INPUT_FILE));
+    }
+}
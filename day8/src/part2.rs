@@ -0,0 +1,127 @@
+use itertools::Itertools;
+
+use aoc::aux::number_theory::crt;
+
+use crate::PuzzleInput;
+use crate::analysis::{analyze, WalkReport};
+
+
+// Every walk eventually repeats: once a walk is at the same node at the end of two passes through the instructions,
+// it repeats the same steps forever. So after a preamble of whole passes, the steps at which a walk is at a Z-node repeat
+// with a period of a whole number of passes. All walks are at a Z-node simultaneously at a step which is congruent,
+// for every walk, to one of its Z-positions within the cycle, which we solve with the Chinese remainder theorem.
+// (For the actual inputs, the preamble is a single pass, every cycle is p * 277 steps long with p prime, and contains a single Z-node
+// 277 steps before its end, so the answer is just the product of the cycle lengths, but we don't rely on that;
+// see `analysis::Assumptions`.)
+
+
+pub fn solve_part2(input: &PuzzleInput) -> u64 {
+    first_simultaneous_z(&analyze(input)).expect("The walks are never at a Z-node simultaneously")
+}
+
+/// The first step at which all walks are at a Z-node, if there is one.
+fn first_simultaneous_z(reports: &[WalkReport]) -> Option<u64> {
+    // Before every walk is in its cycle, just check every step
+    let preamble_len = reports.iter().map(|report| report.preamble_len).max()?;
+    if let Some(step) = (0..preamble_len).find(|&step| reports.iter().all(|report| report.is_z_at(step))) {
+        return Some(step);
+    }
+
+    // After that, solve the system of congruences for every combination of Z-positions, and take the earliest solution
+    reports.iter()
+        .map(|report| report.cycle_z.iter().map(|&z| ((report.preamble_len + z) as i64, report.cycle_len as i64)))
+        .multi_cartesian_product()
+        .filter_map(|congruences| crt(&congruences))
+        .map(|(x, m)| {
+            // The smallest solution that is not before `preamble_len`
+            let (x, m) = (x as u64, m as u64);
+            if x >= preamble_len { x } else { x + (preamble_len - x).div_ceil(m) * m }
+        })
+        .min()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::INPUT_FILE;
+    use aoc::aux::rng::Rng;
+    use aoc::puzzle::read_and_solve_part2;
+    use crate::{parse_input, Day8};
+    use crate::lockstep::brute_force;
+    use super::*;
+
+    const TEST_SOLUTION: u64 = 6;
+    const SOLUTION: u64 = 10151663816849;
+
+    #[test]
+    fn test_part2() {
+        let test_output = read_and_solve_part2::<Day8>("test_input2.txt");
+        assert_eq!(test_output, TEST_SOLUTION);
+    }
+
+    #[test]
+    fn test_solve_part2() {
+        let solution = read_and_solve_part2::<Day8>(INPUT_FILE);
+        assert_eq!(solution, SOLUTION);
+    }
+
+    #[test]
+    fn test_without_shortcut_assumptions() {
+        // 11A is at 11Z after 1 step and then every 3 steps, 22A is at 22Z after 2 steps and then every 2 steps.
+        // The shortcut (product of the cycle lengths) would give 6, but they first meet at step 4.
+        let input = parse_input("\
+L
+
+11A = (11Z, XXX)
+11Z = (11B, XXX)
+11B = (11C, XXX)
+11C = (11Z, XXX)
+22A = (22B, XXX)
+22B = (22Z, XXX)
+22Z = (22B, XXX)
+XXX = (XXX, XXX)
+").unwrap();
+        assert_eq!(solve_part2(&input), 4);
+
+        // Two Z-nodes in a cycle, and a walk that is only at a Z-node during its preamble
+        let input = parse_input("\
+L
+
+11A = (11Z, XXX)
+11Z = (11B, XXX)
+11B = (11Z, XXX)
+22A = (22Z, XXX)
+22Z = (22B, XXX)
+22B = (22C, XXX)
+22C = (22B, XXX)
+XXX = (XXX, XXX)
+").unwrap();
+        assert_eq!(solve_part2(&input), 1);
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        const STEP_LIMIT: u64 = 10_000;
+        let mut rng = Rng(0x2545F4914F6CDD1D);
+        for _ in 0..500 {
+            let instructions: String = (0..1 + rng.below(4)).map(|_| if rng.below(2) == 0 { 'L' } else { 'R' }).collect();
+            let n_nodes = 2 + rng.below(8);
+            let names: Vec<String> = (0..n_nodes).map(|i| format!("{}{}", i, ["A", "Z", "X"][rng.below(3)])).collect();
+            let lines: String = names.iter()
+                .map(|name| format!("{} = ({}, {})\n", name, names[rng.below(n_nodes)], names[rng.below(n_nodes)]))
+                .collect();
+            let input = parse_input(&format!("{}\n\n{}", instructions, lines)).unwrap();
+            let reports = analyze(&input);
+            if reports.is_empty() {
+                continue;
+            }
+            let expected = brute_force(&input, STEP_LIMIT);
+            assert_eq!(first_simultaneous_z(&reports).filter(|&step| step < STEP_LIMIT), expected, "{:?}", input);
+        }
+    }
+
+    #[bench]
+    fn bench_part2(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part2::<Day8>(INPUT_FILE));
+    }
+}
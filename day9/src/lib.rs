@@ -1,51 +1,111 @@
-use std::fs;
-use std::io;
-use std::path::Path;
-use std::str::FromStr;
+#![cfg_attr(test, feature(test))]
 
-pub const INPUT_FILE: &str = "input.txt";
+#[cfg(test)]
+extern crate test;
 
+use std::error::Error;
+use std::fmt;
 
-pub fn read_input(path: impl AsRef<Path>) -> io::Result<String> {
-    fs::read_to_string(path)
+use aoc::aux::numeric::BigInt;
+use aoc::aux::parse_numbers;
+use aoc::error::ParseError;
+use aoc::puzzle::Puzzle;
+
+pub mod part1;
+pub mod sequence;
+pub mod part2;
+
+pub use part1::solve_part1;
+pub use part2::solve_part2;
+pub use sequence::{FitError, Sequence};
+
+
+pub struct Day9;
+
+impl Puzzle for Day9 {
+    type Input = PuzzleInput;
+    type Output1 = i64;
+    type Output2 = i64;
+    type ParseError = InputError;
+
+    fn parse(input: &str) -> Result<PuzzleInput, InputError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &PuzzleInput) -> i64 {
+        solve_part1(input)
+    }
+
+    fn part2(input: &PuzzleInput) -> i64 {
+        solve_part2(input)
+    }
+}
+
+
+pub fn parse_input(input: &str) -> Result<PuzzleInput, InputError> {
+    let histories = input.lines().enumerate().map(|(i, line)| {
+        let values: Vec<i64> = parse_numbers(line).map_err(|err| err.within(input, line))?;
+        Sequence::fit(&values).map_err(|error| InputError::Fit { line: i + 1, error })
+    });
+    let histories = histories.collect::<Result<_, _>>()?;
+    Ok(PuzzleInput { histories })
 }
 
-pub fn write_output(output: impl ToString) {
-    println!("{}", output.to_string());
+/// A line that is not a list of numbers, or a history that no polynomial can be fitted to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputError {
+    Parse(ParseError),
+    Fit { line: usize, error: FitError },
 }
 
-pub fn parse_input(input: &str) -> PuzzleInput {
-    let histories = input.lines().map(parse_numbers);
-    let histories = histories.map(|x| x.unwrap()).collect();
-    PuzzleInput { histories }
+impl From<ParseError> for InputError {
+    fn from(err: ParseError) -> InputError {
+        InputError::Parse(err)
+    }
 }
 
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Parse(err) => err.fmt(f),
+            InputError::Fit { line, error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl Error for InputError {}
+
 #[derive(Clone, Debug)]
 pub struct PuzzleInput {
-    pub histories: Vec<Vec<i64>>,
+    /// Fitted exactly, however long the histories are; the solvers narrow them to the type they compute in
+    pub histories: Vec<Sequence<BigInt>>,
 }
 
 
-/// Parse a whitespace-separated list of numbers
-/// 
-/// # Arguments
-/// 
-/// * `numbers` - A string containing a whitespace-separated list of numbers
-/// 
-/// # Returns
-/// 
-/// A vector of parsed numbers
-/// 
-/// # Example
-/// 
-/// ```
-/// let numbers = "1 2 3 4 5";
-/// let parsed = day9::parse_numbers(numbers);
-/// assert_eq!(parsed, Ok(vec![1, 2, 3, 4, 5]));
-/// ```
-pub fn parse_numbers<T: FromStr>(numbers: &str) -> Result<Vec<T>, <T as FromStr>::Err> {
-    numbers
-        .split_whitespace()
-        .map(|x| x.parse())
-        .collect()
+#[cfg(test)]
+mod tests {
+    use aoc::aux::{read_input, INPUT_FILE};
+    use super::*;
+
+    #[test]
+    fn test_parse_input() {
+        let input = parse_input(&read_input(INPUT_FILE).unwrap()).unwrap();
+        assert_eq!(input.histories.len(), 200);
+        assert!(input.histories.iter().all(|history| history.len() == 21));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("0 3 6 9\n1 2 4 8 16\n").unwrap_err();
+        assert_eq!(err, InputError::Fit { line: 2, error: FitError { len: 5 } });
+        assert_eq!(err.to_string(), "line 2: the differences of 5 values never become constant");
+
+        let err = parse_input("0 3 x 9\n").unwrap_err();
+        assert_eq!(err, InputError::Parse(ParseError::new(1, 5, "x", "a number")));
+
+        // The differences of a long history outgrow an `i128` long before they are found never to become constant
+        let line = [i64::MAX, i64::MIN].map(|value| value.to_string()).join(" ");
+        let err = parse_input(&vec![line; 35].join(" ")).unwrap_err();
+        assert_eq!(err, InputError::Fit { line: 1, error: FitError { len: 70 } });
+    }
 }
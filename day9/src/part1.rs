@@ -0,0 +1,52 @@
+use aoc::aux::numeric::{sum, Checked, Integer};
+
+use crate::PuzzleInput;
+
+
+pub fn solve_part1(input: &PuzzleInput) -> i64 {
+    sum_forward(input, Checked(1)).0
+}
+
+/// The sum of the values `k` steps after the last value of every history, computed in `T`.
+pub fn sum_forward<T: Integer>(input: &PuzzleInput, k: T) -> T {
+    sum(input.histories.iter().map(|history| history.cast::<T>().forward(k.clone())))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::{read_input, INPUT_FILE};
+    use aoc::aux::numeric::BigInt;
+    use aoc::puzzle::read_and_solve_part1;
+    use crate::{parse_input, Day9};
+    use super::*;
+
+    const TEST_SOLUTION: i64 = 114;
+
+    #[test]
+    fn test_part1() {
+        let test_output = read_and_solve_part1::<Day9>("test_input.txt");
+        assert_eq!(test_output, TEST_SOLUTION);
+    }
+
+    #[test]
+    fn test_numeric_types() {
+        let input = parse_input(&read_input(INPUT_FILE).unwrap()).unwrap();
+        let solution = solve_part1(&input);
+        assert_eq!(sum_forward(&input, 1i64), solution);
+        assert_eq!(sum_forward(&input, 1i128), solution as i128);
+        assert_eq!(sum_forward(&input, BigInt::from(1)), BigInt::from(solution));
+
+        // Far enough ahead, the highest-degree terms (18, in this input) overflow even an `i128`
+        let k = 1_000_000_000;
+        let exact = sum_forward(&input, BigInt::from(k));
+        assert!(exact > BigInt::from(i128::MAX));
+        let result = std::panic::catch_unwind(|| sum_forward(&input, Checked(k as i128)));
+        assert!(result.is_err());
+    }
+
+    #[bench]
+    fn bench_part1(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part1::<Day9>(INPUT_FILE));
+    }
+}
@@ -0,0 +1,34 @@
+use aoc::aux::numeric::{sum, Checked, Integer};
+
+use crate::PuzzleInput;
+
+
+pub fn solve_part2(input: &PuzzleInput) -> i64 {
+    sum_backward(input, Checked(1)).0
+}
+
+/// The sum of the values `k` steps before the first value of every history, computed in `T`.
+pub fn sum_backward<T: Integer>(input: &PuzzleInput, k: T) -> T {
+    sum(input.histories.iter().map(|history| history.cast::<T>().backward(k.clone())))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::INPUT_FILE;
+    use aoc::puzzle::read_and_solve_part2;
+    use crate::Day9;
+
+    const TEST_SOLUTION: i64 = 2;
+
+    #[test]
+    fn test_part1() {
+        let test_output = read_and_solve_part2::<Day9>("test_input.txt");
+        assert_eq!(test_output, TEST_SOLUTION);
+    }
+
+    #[bench]
+    fn bench_part1(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part2::<Day9>(INPUT_FILE));
+    }
+}
@@ -0,0 +1,219 @@
+use std::error::Error;
+use std::fmt;
+
+use aoc::aux::numeric::{from_big, BigInt, Integer};
+
+
+/// A sequence of values given by a polynomial of the index, fitted to its first values with finite differences.
+///
+/// The polynomial is kept in Newton's forward-difference form, `f(n) = Σ Δʲf(0) * C(n, j)`,
+/// so that it can be evaluated at any index, before or after the known values.
+/// All arithmetic is done in `T`, which is exact unless it overflows; see [`aoc::aux::numeric`] for the options.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::numeric::BigInt;
+/// use day9::Sequence;
+///
+/// // n² + 1
+/// let sequence: Sequence = Sequence::fit(&[1, 2, 5, 10, 17]).unwrap();
+/// assert_eq!(sequence.degree(), 2);
+/// assert_eq!(sequence.forward(1), 26);
+/// assert_eq!(sequence.forward(1_000_000), 1_000_008_000_017);
+/// assert_eq!(sequence.backward(3), 10);
+///
+/// let sequence = Sequence::<BigInt>::fit(&[1, 2, 5, 10, 17]).unwrap();
+/// let k = BigInt::from(10).pow(30);
+/// assert_eq!(sequence.forward(k.clone()), (k.clone() + 4) * (k + 4) + 1);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sequence<T = i128> {
+    values: Vec<i64>,
+    /// The first entry of every row of differences, up to the first constant row
+    leading_differences: Vec<T>,
+}
+
+/// The differences of a sequence never became constant, so no polynomial can be fitted to it with confidence.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FitError {
+    pub len: usize,
+}
+
+impl<T: Integer> Sequence<T> {
+    /// Fit the polynomial of minimal degree to `values`.
+    ///
+    /// A row of differences only counts as constant if it has at least two entries, so a polynomial of degree `d`
+    /// needs at least `d + 2` values; with fewer, any polynomial of a higher degree would fit as well.
+    pub fn fit(values: &[i64]) -> Result<Sequence<T>, FitError> {
+        let mut row: Vec<T> = values.iter().map(|&value| T::from(value)).collect();
+        let mut leading_differences = vec![];
+        while row.len() >= 2 {
+            leading_differences.push(row[0].clone());
+            if row.iter().all(|x| *x == row[0]) {
+                return Ok(Sequence { values: values.to_vec(), leading_differences });
+            }
+            row = row.windows(2).map(|x| x[1].clone() - x[0].clone()).collect();
+        }
+        Err(FitError { len: values.len() })
+    }
+
+    pub fn values(&self) -> &[i64] {
+        &self.values
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The degree of the polynomial, where a constant sequence has degree 0.
+    pub fn degree(&self) -> usize {
+        self.leading_differences.len() - 1
+    }
+
+    /// The value at index `n`, where the known values are at indices `0..len`.
+    pub fn at(&self, n: T) -> T {
+        // C(n, j) = C(n, j - 1) * (n - j + 1) / j, where the division is exact; this also holds for negative n
+        let mut binomial = T::one();
+        let mut value = T::zero();
+        for (j, difference) in self.leading_differences.iter().enumerate() {
+            if j > 0 {
+                let j = T::from(j as i64);
+                binomial = binomial * (n.clone() - j.clone() + T::one()) / j;
+            }
+            value = value + difference.clone() * binomial.clone();
+        }
+        value
+    }
+
+    /// The value `k` steps after the last known value.
+    pub fn forward(&self, k: T) -> T {
+        self.at(T::from(self.len() as i64 - 1) + k)
+    }
+
+    /// The value `k` steps before the first known value.
+    pub fn backward(&self, k: T) -> T {
+        self.at(T::zero() - k)
+    }
+}
+
+impl Sequence<BigInt> {
+    /// The same sequence, computing in `U`.
+    ///
+    /// Differences grow by up to a factor 2 per row, so they are only guaranteed to fit in an `i128`
+    /// for at most 64 rows; fitting in `BigInt` and narrowing the result to `U` is exact for any number of values,
+    /// and gives the same sequence as fitting in `U` whenever that does not overflow.
+    /// As with any arithmetic in `U`, a `Checked` type panics if a difference does not fit.
+    pub fn cast<U: Integer>(&self) -> Sequence<U> {
+        Sequence {
+            values: self.values.clone(),
+            leading_differences: self.leading_differences.iter().map(from_big).collect(),
+        }
+    }
+}
+
+impl fmt::Display for FitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the differences of {} values never become constant", self.len)
+    }
+}
+
+impl Error for FitError {}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc::aux::numeric::{BigInt, Checked};
+    use super::*;
+
+    #[test]
+    fn test_fit() {
+        let sequence: Sequence = Sequence::fit(&[10, 13, 16, 21, 30, 45]).unwrap();
+        assert_eq!(sequence.degree(), 3);
+        assert_eq!(sequence.forward(1), 68);
+        assert_eq!(sequence.backward(1), 5);
+        assert!((0..6).all(|n| sequence.at(n) == sequence.values()[n as usize] as i128));
+
+        let constant: Sequence<i64> = Sequence::fit(&[7, 7]).unwrap();
+        assert_eq!(constant.degree(), 0);
+        assert_eq!(constant.backward(100), 7);
+        assert_eq!(Sequence::<i64>::fit(&[1, 2, 4, 8, 16]), Err(FitError { len: 5 }));
+        assert_eq!(Sequence::<i64>::fit(&[1]), Err(FitError { len: 1 }));
+        assert_eq!(Sequence::<i64>::fit(&[]), Err(FitError { len: 0 }));
+    }
+
+    #[test]
+    fn test_against_polynomials() {
+        // 3n³ - 5n² + 2n - 7, and the sum of fourth powers, whose Newton coefficients are fractions of the power basis
+        let polynomials: [fn(i128) -> i128; 2] = [
+            |n| 3 * n * n * n - 5 * n * n + 2 * n - 7,
+            |n| n * (n + 1) * (2 * n + 1) * (3 * n * n + 3 * n - 1) / 30,
+        ];
+        for (f, degree) in polynomials.into_iter().zip([3, 5]) {
+            let values: Vec<i64> = (0..10).map(|n| f(n) as i64).collect();
+            let sequence: Sequence = Sequence::fit(&values).unwrap();
+            assert_eq!(sequence.degree(), degree);
+            for k in [0, 1, 2, 17, 1000, 123_456] {
+                assert_eq!(sequence.forward(k), f(9 + k));
+                assert_eq!(sequence.backward(k), f(-k));
+            }
+        }
+    }
+
+    #[test]
+    fn test_big() {
+        let values = [0, 1, 4, 9, 16];
+        let k = 1i128 << 62;
+        assert_eq!(Sequence::<Checked<i128>>::fit(&values).unwrap().forward(Checked(k)), Checked((k + 4).pow(2)));
+        let k = BigInt::from(u64::MAX);
+        assert_eq!(Sequence::<BigInt>::fit(&values).unwrap().forward(k.clone()), (k.clone() + 4) * (k + 4));
+    }
+
+    #[test]
+    fn test_cast() {
+        // Differences of 2⁶⁴ - 1, beyond an `i64`
+        let values = [i64::MIN, i64::MAX, i64::MAX, i64::MIN];
+        let sequence = Sequence::<BigInt>::fit(&values).unwrap();
+        assert_eq!(sequence.cast::<i128>(), Sequence::<i128>::fit(&values).unwrap());
+        assert_eq!(sequence.cast::<Checked<i128>>().forward(Checked(3)), Checked(sequence.cast::<i128>().forward(3)));
+    }
+
+    /// 70 values alternating between `i64::MAX` and `i64::MIN`, whose k-th differences are ±2ᵏ⁻¹(2⁶⁴ - 1).
+    fn alternating() -> Vec<i64> {
+        [i64::MAX, i64::MIN].repeat(35)
+    }
+
+    #[test]
+    fn test_long_history() {
+        assert_eq!(Sequence::<BigInt>::fit(&alternating()), Err(FitError { len: 70 }));
+
+        // Alternating between ±`i64::MAX`, with the phase flipped halfway, so that the 69th differences cancel out;
+        // the 68th are `i64::MAX` times C(68, 34), beyond an `i128`
+        let values: Vec<i64> = (0..70).map(|i| match (i % 2 == 0) == (i < 35) {
+            true => i64::MAX,
+            false => -i64::MAX,
+        }).collect();
+        let sequence = Sequence::<BigInt>::fit(&values).unwrap();
+        assert_eq!(sequence.degree(), 68);
+        let binomial = (1..=34).fold(BigInt::from(1), |binomial, j| binomial * (34 + j) / j);
+        assert_eq!(sequence.leading_differences[68], BigInt::from(i64::MAX) * binomial);
+        assert!(sequence.leading_differences[68] > BigInt::from(i128::MAX));
+        assert!((0..70).all(|n| sequence.at(BigInt::from(n)) == BigInt::from(values[n as usize])));
+    }
+
+    #[test]
+    #[should_panic(expected = "Overflow")]
+    fn test_long_history_overflow() {
+        let _ = Sequence::<Checked<i128>>::fit(&alternating());
+    }
+
+    #[test]
+    #[should_panic(expected = "Overflow")]
+    fn test_overflow() {
+        Sequence::<Checked<i128>>::fit(&[0, 1, 4, 9, 16]).unwrap().forward(Checked(u64::MAX as i128));
+    }
+}
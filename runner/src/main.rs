@@ -0,0 +1,153 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Instant;
+
+use aoc::aux::{read_input, INPUT_FILE};
+
+mod registry;
+
+use registry::{Day, DAYS};
+
+
+const USAGE: &str = "\
+Usage:
+    aoc run [--day <N>] [--part <1|2>] [--input <path>]
+    aoc list
+
+Without --day, every registered day is run. Without --part, both parts are run.
+Without --input, the input is read from dayN/input.txt in the workspace.";
+
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("run") => parse_run_args(&args[1..]).and_then(|run_args| run(&run_args)),
+        Some("list") => {
+            list();
+            Ok(())
+        }
+        Some("-h") | Some("--help") | Some("help") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct RunArgs {
+    day: Option<u32>,
+    part: Option<u32>,
+    input: Option<PathBuf>,
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs, String> {
+    let mut run_args = RunArgs::default();
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        let value = args.next().ok_or_else(|| format!("Missing value for {}\n\n{}", flag, USAGE))?;
+        match flag.as_str() {
+            "--day" | "-d" => run_args.day = Some(value.parse().map_err(|_| format!("Invalid day: {}", value))?),
+            "--part" | "-p" => run_args.part = Some(value.parse().map_err(|_| format!("Invalid part: {}", value))?),
+            "--input" | "-i" => run_args.input = Some(PathBuf::from(value)),
+            _ => return Err(format!("Unknown argument: {}\n\n{}", flag, USAGE)),
+        }
+    }
+    if run_args.day.is_none() && run_args.input.is_some() {
+        return Err("--input requires --day".to_string());
+    }
+    Ok(run_args)
+}
+
+fn run(run_args: &RunArgs) -> Result<(), String> {
+    let days: Vec<&Day> = match run_args.day {
+        Some(day) => vec![registry::find(day).ok_or_else(|| format!("Day {} is not registered", day))?],
+        None => DAYS.iter().collect(),
+    };
+    let parts = match run_args.part {
+        Some(part @ (1 | 2)) => vec![part],
+        Some(part) => return Err(format!("Invalid part: {}", part)),
+        None => vec![1, 2],
+    };
+    for day in days {
+        let input_path = run_args.input.clone().unwrap_or_else(|| default_input_path(day.day));
+        let input = read_input(&input_path).map_err(|err| format!("Error reading {}: {}", input_path.display(), err))?;
+        for &part in &parts {
+            let solver = day.solver(part).expect("Part was validated above");
+            let start = Instant::now();
+            let output = solver(&input).map_err(|err| format!("Error parsing {}: {}", input_path.display(), err))?;
+            let elapsed = start.elapsed();
+            println!("Day {:>2}, part {}: {:<20} ({:.3?})", day.day, part, output, elapsed);
+        }
+    }
+    Ok(())
+}
+
+fn list() {
+    for day in DAYS {
+        println!("Day {:>2}: {}", day.day, default_input_path(day.day).display());
+    }
+}
+
+fn default_input_path(day: u32) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(format!("day{}", day))
+        .join(INPUT_FILE)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_run_args() {
+        let parsed = parse_run_args(&args("--day 17 --part 2 --input foo.txt")).unwrap();
+        assert_eq!(parsed, RunArgs { day: Some(17), part: Some(2), input: Some(PathBuf::from("foo.txt")) });
+
+        let parsed = parse_run_args(&args("")).unwrap();
+        assert_eq!(parsed, RunArgs::default());
+
+        assert!(parse_run_args(&args("--day")).is_err());
+        assert!(parse_run_args(&args("--day x")).is_err());
+        assert!(parse_run_args(&args("--input foo.txt")).is_err());
+        assert!(parse_run_args(&args("--color red")).is_err());
+    }
+
+    #[test]
+    fn test_registry() {
+        let days: Vec<u32> = DAYS.iter().map(|day| day.day).collect();
+        assert_eq!(days, [4, 5, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19]);
+        assert!(registry::find(17).is_some());
+        assert!(registry::find(20).is_none());
+    }
+
+    #[test]
+    fn test_run_test_input() {
+        let test_input = Path::new(env!("CARGO_MANIFEST_DIR")).join("../day17/test_input.txt");
+        let input = read_input(test_input).unwrap();
+        let day = registry::find(17).unwrap();
+        assert_eq!((day.part1)(&input), Ok("102".to_string()));
+        assert_eq!((day.part2)(&input), Ok("94".to_string()));
+    }
+
+    #[test]
+    fn test_run_invalid_input() {
+        let day = registry::find(18).unwrap();
+        let output = (day.part1)("R 6 (#70c710)\nX 5 (#0dc571)\n");
+        assert_eq!(output, Err("line 2, column 1: expected a direction (one of `UDLR`, `NSEW`, `^v<>` or an arrow), found `X`".to_string()));
+    }
+}
@@ -0,0 +1,56 @@
+use aoc::puzzle::Puzzle;
+
+
+/// Parses the raw puzzle input and solves one part of a day, returning the answer as a string.
+pub type Solver = fn(&str) -> Result<String, String>;
+
+pub struct Day {
+    pub day: u32,
+    pub part1: Solver,
+    pub part2: Solver,
+}
+
+impl Day {
+    const fn new<P: Puzzle>(day: u32) -> Day {
+        Day { day, part1: solve_part1::<P>, part2: solve_part2::<P> }
+    }
+
+    pub fn solver(&self, part: u32) -> Option<Solver> {
+        match part {
+            1 => Some(self.part1),
+            2 => Some(self.part2),
+            _ => None,
+        }
+    }
+}
+
+fn solve_part1<P: Puzzle>(input: &str) -> Result<String, String> {
+    let input = P::parse(input).map_err(|err| err.to_string())?;
+    Ok(P::part1(&input).to_string())
+}
+
+fn solve_part2<P: Puzzle>(input: &str) -> Result<String, String> {
+    let input = P::parse(input).map_err(|err| err.to_string())?;
+    Ok(P::part2(&input).to_string())
+}
+
+pub fn find(day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|entry| entry.day == day)
+}
+
+pub const DAYS: &[Day] = &[
+    Day::new::<day4::Day4>(4),
+    Day::new::<day5::Day5>(5),
+    Day::new::<day8::Day8>(8),
+    Day::new::<day9::Day9>(9),
+    Day::new::<day10::Day10>(10),
+    Day::new::<day11::Day11>(11),
+    Day::new::<day12::Day12>(12),
+    Day::new::<day13::Day13>(13),
+    Day::new::<day14::Day14>(14),
+    Day::new::<day15::Day15>(15),
+    Day::new::<day16::Day16>(16),
+    Day::new::<day17::Day17>(17),
+    Day::new::<day18::Day18>(18),
+    Day::new::<day19::Day19>(19),
+];
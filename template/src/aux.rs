@@ -1,73 +1,474 @@
-pub mod aux {
-    use std::array::from_fn;
-    use std::collections::HashMap;
-    use std::hash::Hash;
-
-    /// Check whether an n-dimensional index is within bounds of an n-dimensional shape.
-    /// 
-    /// Returns `Some` with the index as a `Box<[usize]>` if the index is within bounds, and `None` otherwise.
-    /// 
+use std::array::from_fn;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::hash::Hash;
+use std::io::{self, BufRead};
+use std::ops::{Index, IndexMut};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::error::{parse_at, ParseError};
+
+pub mod cycles;
+pub mod geometry;
+pub mod intervals;
+pub mod number_theory;
+pub mod numeric;
+#[doc(hidden)]
+pub mod rng;
+pub mod search;
+
+use geometry::Direction;
+use numeric::Integer;
+
+
+pub const INPUT_FILE: &str = "input.txt";
+pub const TEST_INPUT_FILE: &str = "test_input.txt";
+
+
+pub fn read_input(path: impl AsRef<Path>) -> io::Result<String> {
+    fs::read_to_string(path)
+}
+
+pub fn iter_input_lines(filename: impl AsRef<Path>) -> io::Result<impl Iterator<Item = io::Result<String>>> {
+    let file = fs::File::open(filename)?;
+    Ok(io::BufReader::new(file).lines())
+}
+
+pub fn write_output(output: impl ToString) {
+    println!("{}", output.to_string());
+}
+
+/// Parse a whitespace-separated list of numbers
+///
+/// # Arguments
+///
+/// * `numbers` - A string containing a whitespace-separated list of numbers
+///
+/// # Returns
+///
+/// A vector of parsed numbers, or an error pointing at the first token (relative to `numbers`) that is not a number
+///
+/// # Example
+///
+/// ```
+/// let numbers = "1 2 3 4 5";
+/// let parsed = aoc::aux::parse_numbers(numbers);
+/// assert_eq!(parsed, Ok(vec![1, 2, 3, 4, 5]));
+///
+/// let err = aoc::aux::parse_numbers::<u32>("1 2 three").unwrap_err();
+/// assert_eq!((err.column, err.snippet.as_str()), (5, "three"));
+/// ```
+pub fn parse_numbers<T: FromStr>(numbers: &str) -> Result<Vec<T>, ParseError> {
+    numbers
+        .split_whitespace()
+        .map(|x| parse_at(numbers, x, "a number"))
+        .collect()
+}
+
+/// Parse a rectangular map of characters, such as `#..#\n.##.`, into its entries in row-major order.
+///
+/// Every character is converted with `f`; a character for which `f` returns `None` is reported as an error,
+/// with `expected` describing the valid characters.
+///
+/// # Returns
+///
+/// The entries and the shape `[height, width]` of the map, or an error pointing at the first invalid character or ragged row.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::parse_char_map;
+///
+/// let (entries, shape) = parse_char_map("#.\n.#", "`.` or `#`", |c| match c {
+///     '.' => Some(false),
+///     '#' => Some(true),
+///     _ => None,
+/// }).unwrap();
+/// assert_eq!(entries, vec![true, false, false, true]);
+/// assert_eq!(shape, [2, 2]);
+///
+/// let err = parse_char_map("#.\n.?", "`.` or `#`", |c| (c != '?').then_some(c)).unwrap_err();
+/// assert_eq!((err.line, err.column), (2, 2));
+/// ```
+pub fn parse_char_map<T>(input: &str, expected: &str, f: impl Fn(char) -> Option<T>) -> Result<(Vec<T>, [usize; 2]), ParseError> {
+    let mut entries = Vec::with_capacity(input.len());
+    let mut height = 0;
+    let mut width = None;
+    for line in input.lines() {
+        let mut line_width = 0;
+        for (i, c) in line.char_indices() {
+            if width == Some(line_width) {
+                let err = ParseError::at_char(line, i, "end of row");
+                return Err(err.within(input, line));
+            }
+            let entry = f(c).ok_or_else(|| ParseError::at_char(line, i, expected).within(input, line))?;
+            entries.push(entry);
+            line_width += 1;
+        }
+        match width {
+            None => width = Some(line_width),
+            Some(width) if line_width < width => return Err(ParseError::end_of(line, expected).within(input, line)),
+            _ => (),
+        }
+        height += 1;
+    }
+    match width {
+        Some(width) if width > 0 => Ok((entries, [height, width])),
+        _ => Err(ParseError::end_of(input, expected)),
+    }
+}
+
+/// Check whether an n-dimensional index is within bounds of an n-dimensional shape.
+///
+/// Returns `Some` with the index as a `[usize; DIM]` if the index is within bounds, and `None` otherwise.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::within_bounds;
+///
+/// let shape = [3, 4, 5];
+/// assert_eq!(within_bounds([0, 0, 0], shape), Some([0, 0, 0]));
+/// assert_eq!(within_bounds([2, 3, 4], shape), Some([2, 3, 4]));
+/// assert_eq!(within_bounds([3, 0, 0], shape), None);
+/// assert_eq!(within_bounds([1, -1, 0], shape), None);
+/// ```
+pub fn within_bounds<const DIM: usize>(index: [isize; DIM], shape: [usize; DIM]) -> Option<[usize; DIM]> {
+    for (x, s) in index.into_iter().zip(shape) {
+        if x < 0 || x as usize >= s {
+            return None;
+        }
+    }
+    Some(from_fn(|i| index[i] as usize))
+}
+
+/// The sum of the Manhattan distances between all pairs of `points`, in O(n log n).
+///
+/// Per axis, after sorting, the `i`-th coordinate is the larger one in `i` pairs and the smaller one in `n - 1 - i` pairs.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::sum_pairwise_manhattan;
+///
+/// // 3 + 4 + 7
+/// assert_eq!(sum_pairwise_manhattan(&[[0i64, 0], [0, 3], [4, 3]]), 14);
+/// assert_eq!(sum_pairwise_manhattan::<i64, 2>(&[]), 0);
+/// ```
+pub fn sum_pairwise_manhattan<T: Integer, const N: usize>(points: &[[T; N]]) -> T {
+    let n = points.len() as i64;
+    (0..N).map(|axis| {
+        let mut values: Vec<T> = points.iter().map(|point| point[axis].clone()).collect();
+        values.sort();
+        numeric::sum(values.into_iter().enumerate().map(|(i, value)| value * T::from(2 * i as i64 - n + 1)))
+    }).fold(T::zero(), |total, axis_total| total + axis_total)
+}
+
+/// Find the first repetition in a sequence of values.
+///
+/// The function `f` is first applied to the initial value `start` and then recursively to the result of the previous application.
+///
+/// Returns a tuple `(i, history)` where `i` is the index of the first occurrence of the repeating value in `history` and `history` is the sequence of values generated by `f`, including the starting value, but excluding the first repeated value.
+/// Specifically, it is always true that `f(&history.last().unwrap()) == history[i]`.
+///
+/// Every value is kept in memory. For values too big for that, see [`cycles::brent`].
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::until_repeat;
+///
+/// let start = 0;
+/// let (i, history) = until_repeat(&start, |x| (x + 1) % 5);
+/// assert_eq!(i, 0);
+/// assert_eq!(&history[..], &[0, 1, 2, 3, 4]);
+///
+/// let start = 7;
+/// let (i, history) = until_repeat(&start, |x| (x + 1) % 3);
+/// assert_eq!(i, 1);
+/// assert_eq!(&history[..], &[7, 2, 0, 1]);
+/// ```
+pub fn until_repeat<T>(start: &T, f: impl FnMut(&T) -> T) -> (usize, Box<[T]>) where T: Clone + Eq + Hash {
+    until_repeat_by_key(start, f, T::clone)
+}
+
+/// Like [`until_repeat`], but recognize repeated values by `key(value)` instead of by the values themselves.
+///
+/// This avoids keeping a second copy of every value for lookups when a cheaper projection identifies them,
+/// e.g. a hash or a single field. Two values with the same key are taken to be equal, so the key must be unique among the values visited.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::until_repeat_by_key;
+///
+/// // The counter keeps growing, but the state repeats as far as the phase is concerned
+/// let (i, history) = until_repeat_by_key(&(0, 2), |&(counter, phase)| (counter + 1, (phase + 1) % 3), |&(_, phase)| phase);
+/// assert_eq!(i, 0);
+/// assert_eq!(&history[..], &[(0, 2), (1, 0), (2, 1)]);
+/// ```
+pub fn until_repeat_by_key<T, K>(start: &T, mut f: impl FnMut(&T) -> T, mut key: impl FnMut(&T) -> K) -> (usize, Box<[T]>)
+where
+    T: Clone,
+    K: Eq + Hash,
+{
+    let mut seen_map: HashMap<K, usize> = HashMap::new();
+    let mut seen_vec: Vec<T> = Vec::new();
+    let mut current = start.clone();
+    for t in 0.. {
+        if let Some(&rep_index) = seen_map.get(&key(&current)) {
+            return (rep_index, seen_vec.into_boxed_slice());
+        }
+        seen_map.insert(key(&current), t);
+        let next = f(&current);
+        seen_vec.push(current);
+        current = next;
+    }
+    unreachable!()
+}
+
+/// Find the value after `n` applications of `f` to `start`, skipping ahead once the sequence of values repeats.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::state_at;
+///
+/// assert_eq!(state_at(&7, |x| (x + 1) % 3, 2), 0);
+/// assert_eq!(state_at(&7, |x| (x + 1) % 3, 1_000_000_000_000), 2);
+/// ```
+pub fn state_at<T>(start: &T, f: impl FnMut(&T) -> T, n: usize) -> T where T: Clone + Eq + Hash {
+    let (preamble_len, history) = until_repeat(start, f);
+    let cycle_len = history.len() - preamble_len;
+    let i = if n < preamble_len { n } else { preamble_len + (n - preamble_len) % cycle_len };
+    history[i].clone()
+}
+
+
+/// A rectangular 2D grid stored in row-major order, indexed by `(y, x)`.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::Grid;
+///
+/// let grid = Grid::parse("123\n456", "a digit", |c| c.to_digit(10)).unwrap();
+/// assert_eq!(grid.shape(), [2, 3]);
+/// assert_eq!(grid[(1, 0)], 4);
+/// assert_eq!(grid.get(-1, 0), None);
+/// assert_eq!(grid.to_string(), "123\n456\n");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    height: usize,
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Create a grid from its cells in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cells` does not contain exactly `height * width` entries.
+    pub fn new(height: usize, width: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(cells.len(), height * width, "Grid of shape [{}, {}] needs {} cells", height, width, height * width);
+        Grid { height, width, cells }
+    }
+
+    pub fn from_fn(height: usize, width: usize, mut f: impl FnMut(usize, usize) -> T) -> Grid<T> {
+        let cells = (0..height * width).map(|i| f(i / width, i % width)).collect();
+        Grid { height, width, cells }
+    }
+
+    /// Parse a rectangular map of characters, converting each character with `f` (see [`parse_char_map`]).
+    pub fn parse(input: &str, expected: &str, f: impl Fn(char) -> Option<T>) -> Result<Grid<T>, ParseError> {
+        let (cells, [height, width]) = parse_char_map(input, expected, f)?;
+        Ok(Grid { height, width, cells })
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn shape(&self) -> [usize; 2] {
+        [self.height, self.width]
+    }
+
+    /// Convert a signed position into an index, if it lies within the grid.
+    pub fn checked_index(&self, y: isize, x: isize) -> Option<(usize, usize)> {
+        within_bounds([y, x], self.shape()).map(|[y, x]| (y, x))
+    }
+
+    pub fn get(&self, y: isize, x: isize) -> Option<&T> {
+        self.checked_index(y, x).map(|index| &self[index])
+    }
+
+    pub fn get_mut(&mut self, y: isize, x: isize) -> Option<&mut T> {
+        self.checked_index(y, x).map(|index| &mut self[index])
+    }
+
+    /// Iterate over all cells in row-major order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    /// Iterate over all cells in row-major order, together with their index.
+    pub fn indexed_iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells.iter().enumerate().map(move |(i, cell)| ((i / width, i % width), cell))
+    }
+
+    /// Find the index of the first cell (in row-major order) satisfying `predicate`.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<(usize, usize)> {
+        self.cells.iter().position(predicate).map(|i| (i / self.width, i % self.width))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(x < self.width, "Column {} out of bounds for width {}", x, self.width);
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    /// Iterate over the rows as slices; a grid of width 0 still has `height` (empty) rows.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T> + ExactSizeIterator> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The index one step from `(y, x)` in `direction`, if it lies within the grid.
+    pub fn step(&self, (y, x): (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+        self.checked_index(y as isize + direction.dy(), x as isize + direction.dx())
+    }
+
+    /// The indices of the (up to) 4 orthogonal neighbours of `(y, x)` within the grid.
+    ///
     /// # Example
-    /// 
+    ///
     /// ```
-    /// use aoc::aux::within_bounds;
-    /// 
-    /// let shape = [3, 4, 5];
-    /// assert_eq!(within_bounds(&[0, 0, 0], &shape), Some(Box::new([0, 0, 0])));
-    /// assert_eq!(within_bounds(&[2, 3, 4], &shape), Some(Box::new([2, 3, 4])));
-    /// assert_eq!(within_bounds(&[3, 0, 0], &shape), None);
-    /// assert_eq!(within_bounds(&[1, -1, 0], &shape), None);
+    /// use aoc::aux::Grid;
+    ///
+    /// let grid = Grid::new(2, 3, vec![0; 6]);
+    /// let neighbours: Vec<_> = grid.neighbours4((0, 1)).collect();
+    /// assert_eq!(neighbours, vec![(1, 1), (0, 0), (0, 2)]);
     /// ```
-    pub fn within_bounds<const DIM: usize>(index: [isize; DIM], shape: [usize; DIM]) -> Option<[usize; DIM]> {
-        let mut index_unsigned: [usize; DIM] = from_fn(|i| index[i] as usize);
-        for (i, (&x, &s)) in index.iter().zip(shape.iter()).enumerate() {
-            if x < 0 || x as usize >= s {
-                return None;
-            }
-            index_unsigned[i] = x as usize;
-        }
-        Some(index_unsigned.into())
+    pub fn neighbours4(&self, (y, x): (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Direction::ALL.into_iter().filter_map(move |direction| self.step((y, x), direction))
     }
 
-    /// Find the first repetition in a sequence of values.
-    /// 
-    /// The function `f` is first applied to the initial value `start` and then recursively to the result of the previous application.
-    /// 
-    /// Returns a tuple `(i, history)` where `i` is the index of the first occurrence of the repeating value in `history` and `history` is the sequence of values generated by `f`, including the starting value, but excluding the first repeated value.
-    /// Specifically, it is always true that `f(&history.last().unwrap()) == history[i]`.
-    /// 
+    /// The indices of the (up to) 8 orthogonal and diagonal neighbours of `(y, x)` within the grid.
+    ///
     /// # Example
-    /// 
+    ///
     /// ```
-    /// use aoc::aux::until_repeat;
-    /// 
-    /// let start = 0;
-    /// let f = |x| (x + 1) % 5;
-    /// let (i, history) = until_repeat(&start, f);
-    /// assert_eq!(i, 0);
-    /// assert_eq!(&history[..], &[0, 1, 2, 3, 4]);
-    /// 
-    /// let start = 7;
-    /// let f = |x| (x + 1) % 3;
-    /// let (i, history) = until_repeat(&start, f);
-    /// assert_eq!(i, 1);
-    /// assert_eq!(&history[..], &[7, 2, 0, 1]);
+    /// use aoc::aux::Grid;
+    ///
+    /// let grid = Grid::new(3, 3, vec![0; 9]);
+    /// assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+    /// assert_eq!(grid.neighbours8((0, 0)).collect::<Vec<_>>(), vec![(0, 1), (1, 0), (1, 1)]);
     /// ```
-    pub fn until_repeat<T>(start: &T, f: fn(&T) -> T) -> (usize, Box<[T]>) where T: Clone + Eq + Hash {
-        let mut seen_map: HashMap<T, usize> = HashMap::new();
-        let mut seen_vec: Vec<T> = Vec::new();
-        let mut current = start.clone();
-        seen_map.insert(start.clone(), 0);
-        seen_vec.push(start.clone());
-        for t in 1.. {
-            current = f(&current);
-            if seen_map.get(&current).is_some() {
-                break;
+    pub fn neighbours8(&self, (y, x): (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        const OFFSETS: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+        OFFSETS.into_iter().filter_map(move |(dy, dx)| self.checked_index(y as isize + dy, x as isize + dx))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { height: self.height, width: self.width, cells: self.cells.iter().map(f).collect() }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Mirror the grid along its main diagonal, so that rows become columns.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aoc::aux::Grid;
+    ///
+    /// let grid = Grid::new(2, 3, vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(grid.transposed(), Grid::new(3, 2, vec![1, 4, 2, 5, 3, 6]));
+    /// ```
+    pub fn transposed(&self) -> Grid<T> {
+        Grid::from_fn(self.width, self.height, |y, x| self[(x, y)].clone())
+    }
+
+    /// Rotate the grid by a quarter turn clockwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aoc::aux::Grid;
+    ///
+    /// let grid = Grid::new(2, 3, vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(grid.rotated_clockwise(), Grid::new(3, 2, vec![4, 1, 5, 2, 6, 3]));
+    /// assert_eq!(grid.rotated_clockwise().rotated_counterclockwise(), grid);
+    /// ```
+    pub fn rotated_clockwise(&self) -> Grid<T> {
+        Grid::from_fn(self.width, self.height, |y, x| self[(self.height - 1 - x, y)].clone())
+    }
+
+    /// Rotate the grid by a quarter turn counterclockwise.
+    pub fn rotated_counterclockwise(&self) -> Grid<T> {
+        Grid::from_fn(self.width, self.height, |y, x| self[(x, self.width - 1 - y)].clone())
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (y, x): (usize, usize)) -> &T {
+        assert!(y < self.height && x < self.width, "Index ({}, {}) out of bounds for shape [{}, {}]", y, x, self.height, self.width);
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (y, x): (usize, usize)) -> &mut T {
+        assert!(y < self.height && x < self.width, "Index ({}, {}) out of bounds for shape [{}, {}]", y, x, self.height, self.width);
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
             }
-            seen_map.insert(current.clone(), t);
-            seen_vec.push(current.clone());
+            writeln!(f)?;
         }
-        let rep_index = seen_map.get(&current).unwrap();
-        (*rep_index, seen_vec.into_boxed_slice())
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_without_columns() {
+        let grid: Grid<u8> = Grid::new(2, 0, vec![]);
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[], []]);
+        assert_eq!(grid.columns().count(), 0);
+        assert_eq!(grid.to_string(), "\n\n");
+
+        let transposed = Grid::<u8>::new(0, 3, vec![]).transposed();
+        assert_eq!(transposed.shape(), [3, 0]);
+        assert_eq!(transposed.rows().count(), 3);
+        assert_eq!(transposed.to_string(), "\n\n\n");
+        assert_eq!(transposed.transposed().rows().count(), 0);
     }
 }
@@ -0,0 +1,150 @@
+// Cycle detection for sequences x0, f(x0), f(f(x0)), ... that only ever keeps a few values in memory,
+// for values too big to store all of them, as `until_repeat` does. The price is that `f` is evaluated more often,
+// and that values have to be recomputed to be retrieved.
+
+
+/// The shape of a sequence of values generated by repeatedly applying a function:
+/// `preamble_len` values that occur only once, followed by a cycle of `cycle_len` values that repeats forever.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CycleInfo {
+    pub preamble_len: usize,
+    pub cycle_len: usize,
+}
+
+impl CycleInfo {
+    /// The smallest index at which the sequence has the same value as at index `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.preamble_len {
+            n
+        } else {
+            self.preamble_len + (n - self.preamble_len) % self.cycle_len
+        }
+    }
+
+    /// The value at index `n` of the sequence starting at `start`, recomputed with at most `preamble_len + cycle_len - 1` applications of `f`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aoc::aux::cycles::brent;
+    ///
+    /// let f = |x: &u64| (x * x + 1) % 255;
+    /// let info = brent(&3, f);
+    /// assert_eq!(info.state_at(&3, f, 1_000_000_000), aoc::aux::state_at(&3, f, 1_000_000_000));
+    /// ```
+    pub fn state_at<T: Clone>(&self, start: &T, mut f: impl FnMut(&T) -> T, n: usize) -> T {
+        (0..self.reduce(n)).fold(start.clone(), |state, _| f(&state))
+    }
+}
+
+/// Find the preamble and cycle length of the sequence `start, f(start), f(f(start)), ...` with Brent's algorithm.
+///
+/// Only two values are kept at any time. `f` is applied about `2 * preamble_len + 3 * cycle_len` times.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::cycles::{brent, CycleInfo};
+///
+/// // 7, 2, 0, 1, 2, 0, 1, ...
+/// assert_eq!(brent(&7, |x| (x + 1) % 3), CycleInfo { preamble_len: 1, cycle_len: 3 });
+/// ```
+pub fn brent<T: Clone + Eq>(start: &T, mut f: impl FnMut(&T) -> T) -> CycleInfo {
+    // Find the cycle length: the hare runs ahead, and the tortoise teleports to it whenever the hare has made a power of 2 steps
+    let mut power = 1;
+    let mut cycle_len = 1;
+    let mut tortoise = start.clone();
+    let mut hare = f(start);
+    while tortoise != hare {
+        if power == cycle_len {
+            tortoise = hare.clone();
+            power *= 2;
+            cycle_len = 0;
+        }
+        hare = f(&hare);
+        cycle_len += 1;
+    }
+
+    // Find the preamble length: with the hare one cycle ahead of the tortoise, they first meet at the start of the cycle
+    let mut tortoise = start.clone();
+    let mut hare = (0..cycle_len).fold(start.clone(), |hare, _| f(&hare));
+    let mut preamble_len = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        preamble_len += 1;
+    }
+    CycleInfo { preamble_len, cycle_len }
+}
+
+/// Find the preamble and cycle length of the sequence `start, f(start), f(f(start)), ...` with Floyd's algorithm.
+///
+/// Only two values are kept at any time. Usually slower than [`brent`], since `f` is applied about `3 * preamble_len + 4 * cycle_len` times.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::cycles::{floyd, CycleInfo};
+///
+/// // 7, 2, 0, 1, 2, 0, 1, ...
+/// assert_eq!(floyd(&7, |x| (x + 1) % 3), CycleInfo { preamble_len: 1, cycle_len: 3 });
+/// ```
+pub fn floyd<T: Clone + Eq>(start: &T, mut f: impl FnMut(&T) -> T) -> CycleInfo {
+    // The hare runs at twice the speed of the tortoise, so they meet once both are in the cycle
+    let mut tortoise = f(start);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        let halfway = f(&hare);
+        hare = f(&halfway);
+    }
+
+    // The meeting point is a multiple of the cycle length away from the start, so moving in lockstep from there
+    // and from the start, they first meet at the start of the cycle
+    let mut tortoise = start.clone();
+    let mut preamble_len = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        preamble_len += 1;
+    }
+
+    let mut hare = f(&tortoise);
+    let mut cycle_len = 1;
+    while tortoise != hare {
+        hare = f(&hare);
+        cycle_len += 1;
+    }
+    CycleInfo { preamble_len, cycle_len }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aux::until_repeat;
+
+    #[test]
+    fn test_against_until_repeat() {
+        for modulus in 1..60u64 {
+            for multiplier in 0..10 {
+                let f = |x: &u64| (x * x * multiplier + 7) % modulus;
+                let (preamble_len, history) = until_repeat(&(modulus + 1), f);
+                let expected = CycleInfo { preamble_len, cycle_len: history.len() - preamble_len };
+                assert_eq!(brent(&(modulus + 1), f), expected);
+                assert_eq!(floyd(&(modulus + 1), f), expected);
+                for n in 0..2 * history.len() {
+                    assert_eq!(expected.state_at(&(modulus + 1), f, n), (0..n).fold(modulus + 1, |x, _| f(&x)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pure_cycle() {
+        let info = brent(&0, |x| (x + 1) % 5);
+        assert_eq!(info, CycleInfo { preamble_len: 0, cycle_len: 5 });
+        assert_eq!(info.reduce(12), 2);
+        assert_eq!(floyd(&4, |_| 4), CycleInfo { preamble_len: 0, cycle_len: 1 });
+    }
+}
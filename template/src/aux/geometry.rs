@@ -0,0 +1,350 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use crate::aux::within_bounds;
+use crate::error::ParseError;
+
+
+/// One of the four orthogonal directions on a grid, with `y` pointing down (as in the row index of a map).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+    pub const EXPECTED: &'static str = "a direction (one of `UDLR`, `NSEW`, `^v<>` or an arrow)";
+
+    pub fn dy(self) -> isize {
+        match self {
+            Direction::Up => -1,
+            Direction::Down => 1,
+            Direction::Left | Direction::Right => 0,
+        }
+    }
+
+    pub fn dx(self) -> isize {
+        match self {
+            Direction::Left => -1,
+            Direction::Right => 1,
+            Direction::Up | Direction::Down => 0,
+        }
+    }
+
+    /// The unit vector pointing in this direction.
+    pub fn as_vector(self) -> Vector {
+        Vector { dy: self.dy(), dx: self.dx() }
+    }
+
+    /// # Example
+    ///
+    /// ```
+    /// use aoc::aux::geometry::Direction;
+    ///
+    /// assert_eq!(Direction::Up.turn_left(), Direction::Left);
+    /// assert_eq!(Direction::Left.turn_left(), Direction::Down);
+    /// ```
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        self.turn_left().reverse()
+    }
+
+    pub fn reverse(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    pub fn orientation(self) -> Orientation {
+        match self {
+            Direction::Up | Direction::Down => Orientation::Vertical,
+            Direction::Left | Direction::Right => Orientation::Horizontal,
+        }
+    }
+
+    /// Parse a direction from a letter (`U`/`D`/`L`/`R` or compass `N`/`S`/`W`/`E`) or an arrow (`^v<>` or `↑↓←→`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aoc::aux::geometry::Direction;
+    ///
+    /// assert_eq!(Direction::from_char('U'), Some(Direction::Up));
+    /// assert_eq!(Direction::from_char('E'), Some(Direction::Right));
+    /// assert_eq!(Direction::from_char('v'), Some(Direction::Down));
+    /// assert_eq!(Direction::from_char('←'), Some(Direction::Left));
+    /// assert_eq!(Direction::from_char('x'), None);
+    /// ```
+    pub fn from_char(c: char) -> Option<Direction> {
+        match c {
+            'U' | 'N' | '^' | '↑' => Some(Direction::Up),
+            'D' | 'S' | 'v' | '↓' => Some(Direction::Down),
+            'L' | 'W' | '<' | '←' => Some(Direction::Left),
+            'R' | 'E' | '>' | '→' => Some(Direction::Right),
+            _ => None,
+        }
+    }
+
+    pub fn as_letter(self) -> char {
+        match self {
+            Direction::Up => 'U',
+            Direction::Down => 'D',
+            Direction::Left => 'L',
+            Direction::Right => 'R',
+        }
+    }
+
+    pub fn as_arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Right => '>',
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Direction, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::from_char(c).ok_or_else(|| ParseError::at(s, s, Direction::EXPECTED)),
+            _ => Err(ParseError::at(s, s, Direction::EXPECTED)),
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_letter())
+    }
+}
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+}
+
+impl Orientation {
+    /// The two directions along this orientation.
+    pub fn directions(self) -> [Direction; 2] {
+        match self {
+            Orientation::Horizontal => [Direction::Left, Direction::Right],
+            Orientation::Vertical => [Direction::Up, Direction::Down],
+        }
+    }
+
+    pub fn orthogonal(self) -> Orientation {
+        match self {
+            Orientation::Horizontal => Orientation::Vertical,
+            Orientation::Vertical => Orientation::Horizontal,
+        }
+    }
+
+    /// The two directions orthogonal to this orientation, i.e. the possible turns.
+    pub fn orthogonals(self) -> [Direction; 2] {
+        self.orthogonal().directions()
+    }
+}
+
+
+/// A position on an unbounded grid, as `(y, x)` with `y` pointing down.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::geometry::{Direction, Point, Vector};
+///
+/// let p = Point::new(2, 3);
+/// assert_eq!(p + Direction::Up.as_vector() * 3, Point::new(-1, 3));
+/// assert_eq!(Point::new(5, 1) - p, Vector::new(3, -2));
+/// assert_eq!(p.step(Direction::Left), Point::new(2, 2));
+/// assert_eq!(p.manhattan_distance(Point::new(0, 0)), 5);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub y: isize,
+    pub x: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { y: 0, x: 0 };
+
+    pub fn new(y: isize, x: isize) -> Point {
+        Point { y, x }
+    }
+
+    pub fn from_index((y, x): (usize, usize)) -> Point {
+        Point { y: y as isize, x: x as isize }
+    }
+
+    /// Convert the point into an index into a map of the given `[height, width]`, if it lies within bounds.
+    pub fn to_index(self, shape: [usize; 2]) -> Option<(usize, usize)> {
+        within_bounds([self.y, self.x], shape).map(|[y, x]| (y, x))
+    }
+
+    pub fn step(self, direction: Direction) -> Point {
+        self + direction.as_vector()
+    }
+
+    pub fn manhattan_distance(self, other: Point) -> usize {
+        (self - other).manhattan_len()
+    }
+}
+
+/// A displacement between two [`Point`]s.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vector {
+    pub dy: isize,
+    pub dx: isize,
+}
+
+impl Vector {
+    pub const ZERO: Vector = Vector { dy: 0, dx: 0 };
+
+    pub fn new(dy: isize, dx: isize) -> Vector {
+        Vector { dy, dx }
+    }
+
+    pub fn manhattan_len(self) -> usize {
+        self.dy.unsigned_abs() + self.dx.unsigned_abs()
+    }
+
+    /// The direction of a non-zero vector along one of the axes, and `None` otherwise.
+    pub fn direction(self) -> Option<Direction> {
+        match (self.dy.signum(), self.dx.signum()) {
+            (-1, 0) => Some(Direction::Up),
+            (1, 0) => Some(Direction::Down),
+            (0, -1) => Some(Direction::Left),
+            (0, 1) => Some(Direction::Right),
+            _ => None,
+        }
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Vector) -> Point {
+        Point { y: self.y + rhs.dy, x: self.x + rhs.dx }
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Vector) -> Point {
+        self + -rhs
+    }
+}
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, rhs: Vector) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub<Point> for Point {
+    type Output = Vector;
+
+    fn sub(self, rhs: Point) -> Vector {
+        Vector { dy: self.y - rhs.y, dx: self.x - rhs.x }
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, rhs: Vector) -> Vector {
+        Vector { dy: self.dy + rhs.dy, dx: self.dx + rhs.dx }
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, rhs: Vector) -> Vector {
+        self + -rhs
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector { dy: -self.dy, dx: -self.dx }
+    }
+}
+
+impl Mul<isize> for Vector {
+    type Output = Vector;
+
+    fn mul(self, rhs: isize) -> Vector {
+        Vector { dy: self.dy * rhs, dx: self.dx * rhs }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_left().turn_left(), direction.reverse());
+            assert_eq!(direction.turn_right().orientation(), direction.orientation().orthogonal());
+            assert_eq!(direction.as_vector().direction(), Some(direction));
+            assert_eq!(Direction::from_char(direction.as_arrow()), Some(direction));
+            assert_eq!(direction.to_string().parse(), Ok(direction));
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+    }
+
+    #[test]
+    fn test_orientation() {
+        assert_eq!(Orientation::Vertical.orthogonals(), [Direction::Left, Direction::Right]);
+        assert_eq!(Orientation::Horizontal.orthogonals(), [Direction::Up, Direction::Down]);
+    }
+
+    #[test]
+    fn test_to_index() {
+        assert_eq!(Point::new(1, 2).to_index([2, 3]), Some((1, 2)));
+        assert_eq!(Point::new(1, 3).to_index([2, 3]), None);
+        assert_eq!(Point::new(-1, 0).to_index([2, 3]), None);
+        assert_eq!(Point::from_index((1, 2)), Point::new(1, 2));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = "UD".parse::<Direction>().unwrap_err();
+        assert_eq!((err.line, err.column, err.snippet.as_str()), (1, 1, "UD"));
+    }
+}
@@ -0,0 +1,450 @@
+use std::array::from_fn;
+use std::iter::{Product, Sum};
+use std::ops::{Add, Range, RangeInclusive, Sub};
+
+
+// Sets of integers stored as sorted lists of disjoint ranges, and unions of axis-aligned boxes built from them.
+// All ranges are half-open (`start..end`); inclusive ranges are converted on the way in.
+
+
+/// A type that can be used as an endpoint of an interval: an integer, in practice.
+pub trait Endpoint: Copy + Ord + Add<Output = Self> + Sub<Output = Self> + From<u8> {}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T> + From<u8>> Endpoint for T {}
+
+
+/// A set of integers, stored as a normalized list of ranges: sorted, non-empty, and neither overlapping nor touching.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::intervals::IntervalSet;
+///
+/// let a: IntervalSet<u64> = [0..3, 2..5, 3..4, 6..9].into_iter().collect();
+/// assert_eq!(a.ranges(), [0..5, 6..9]);
+/// let b = IntervalSet::from(4..7);
+/// assert_eq!(a.union(&b).ranges(), [0..9]);
+/// assert_eq!(a.intersection(&b).ranges(), [4..5, 6..7]);
+/// assert_eq!(a.difference(&b).ranges(), [0..4, 7..9]);
+/// assert_eq!(a.complement(0..10).ranges(), [5..6, 9..10]);
+/// assert_eq!(a.len(), 8);
+/// assert!(a.contains(&8) && !a.contains(&5));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Endpoint> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet { ranges: Vec::new() }
+    }
+
+    pub fn from_inclusive(range: RangeInclusive<T>) -> IntervalSet<T> {
+        let (start, end) = range.into_inner();
+        if start > end {
+            IntervalSet::new()
+        } else {
+            IntervalSet::from(start..end + T::from(1))
+        }
+    }
+
+    /// The ranges making up the set, sorted by start
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of integers in the set
+    pub fn len(&self) -> T {
+        self.ranges.iter().fold(T::from(0), |len, range| len + (range.end - range.start))
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let i = self.ranges.partition_point(|range| range.end <= *value);
+        self.ranges.get(i).is_some_and(|range| range.start <= *value)
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.ranges.last().map(|range| range.end - T::from(1))
+    }
+
+    pub fn is_disjoint(&self, other: &IntervalSet<T>) -> bool {
+        let (mut i, mut j) = (0, 0);
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            if a.start < b.end && b.start < a.end {
+                return false;
+            }
+            if a.end <= b.end { i += 1 } else { j += 1 }
+        }
+        true
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        *self = self.union(&IntervalSet::from(range));
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        self.combine(other, |a, b| a || b)
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        self.combine(other, |a, b| a && b)
+    }
+
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        self.combine(other, |a, b| a && !b)
+    }
+
+    /// The integers within `bounds` that are not in the set
+    pub fn complement(&self, bounds: Range<T>) -> IntervalSet<T> {
+        IntervalSet::from(bounds).difference(self)
+    }
+
+    /// Cut the number line at every endpoint of either set, and keep the pieces for which `keep` holds.
+    fn combine(&self, other: &IntervalSet<T>, keep: impl Fn(bool, bool) -> bool) -> IntervalSet<T> {
+        let mut bounds: Vec<T> = self.ranges.iter().chain(&other.ranges)
+            .flat_map(|range| [range.start, range.end])
+            .collect();
+        bounds.sort_unstable();
+        bounds.dedup();
+        bounds.windows(2)
+            .filter(|pair| keep(self.contains(&pair[0]), other.contains(&pair[0])))
+            .map(|pair| pair[0]..pair[1])
+            .collect()
+    }
+}
+
+impl<T: Endpoint> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet::new()
+    }
+}
+
+impl<T: Endpoint> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        [range].into_iter().collect()
+    }
+}
+
+/// Collect possibly empty, overlapping and unsorted ranges into a normalized set
+impl<T: Endpoint> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut ranges: Vec<Range<T>> = iter.into_iter().filter(|range| !range.is_empty()).collect();
+        ranges.sort_unstable_by_key(|range| range.start);
+        let ranges = ranges.into_iter().fold(Vec::new(), |mut merged: Vec<Range<T>>, range| {
+            match merged.last_mut() {
+                Some(last) if last.end >= range.start => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+            merged
+        });
+        IntervalSet { ranges }
+    }
+}
+
+
+/// An axis-aligned box in `N` dimensions, whose extent along every axis is an [`IntervalSet`].
+///
+/// Since the extents need not be single ranges, a cuboid may consist of several separate boxes.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Cuboid<T, const N: usize> {
+    pub axes: [IntervalSet<T>; N],
+}
+
+impl<T: Endpoint, const N: usize> Cuboid<T, N> {
+    pub fn new(axes: [IntervalSet<T>; N]) -> Cuboid<T, N> {
+        Cuboid { axes }
+    }
+
+    pub fn from_ranges(ranges: [Range<T>; N]) -> Cuboid<T, N> {
+        Cuboid { axes: ranges.map(IntervalSet::from) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.axes.iter().any(IntervalSet::is_empty)
+    }
+
+    pub fn contains(&self, point: &[T; N]) -> bool {
+        self.axes.iter().zip(point).all(|(axis, value)| axis.contains(value))
+    }
+
+    /// The number of integer points in the cuboid, computed in a type `V` wide enough not to overflow
+    pub fn volume<V: From<T> + Product>(&self) -> V {
+        self.axes.iter().map(|axis| V::from(axis.len())).product()
+    }
+
+    pub fn is_disjoint(&self, other: &Cuboid<T, N>) -> bool {
+        self.axes.iter().zip(&other.axes).any(|(a, b)| a.is_disjoint(b))
+    }
+
+    pub fn intersection(&self, other: &Cuboid<T, N>) -> Cuboid<T, N> {
+        Cuboid { axes: from_fn(|i| self.axes[i].intersection(&other.axes[i])) }
+    }
+
+    /// Split the part of `self` outside of `other` into disjoint, non-empty cuboids.
+    ///
+    /// Peels off the part sticking out along the first axis, then along the second axis from what is left, etc.
+    pub fn difference(&self, other: &Cuboid<T, N>) -> Vec<Cuboid<T, N>> {
+        if self.is_disjoint(other) {
+            return if self.is_empty() { vec![] } else { vec![self.clone()] };
+        }
+        let mut pieces = Vec::new();
+        let mut rest = self.clone();
+        for axis in 0..N {
+            let outside = rest.axes[axis].difference(&other.axes[axis]);
+            if !outside.is_empty() {
+                let mut piece = rest.clone();
+                piece.axes[axis] = outside;
+                pieces.push(piece);
+            }
+            rest.axes[axis] = rest.axes[axis].intersection(&other.axes[axis]);
+        }
+        pieces
+    }
+}
+
+
+/// A set of points in `N` dimensions, stored as a union of disjoint [`Cuboid`]s.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::intervals::{BoxSet, Cuboid, IntervalSet};
+///
+/// let mut set = BoxSet::from(Cuboid::from_ranges([0u32..4, 0..4]));
+/// set.insert(Cuboid::from_ranges([2..6, 2..6]));
+/// assert_eq!(set.volume::<u32>(), 28);
+///
+/// let (left, right) = set.partition(1, &IntervalSet::from(0..3));
+/// assert_eq!(left.volume::<u32>(), 14);
+/// assert_eq!(right.volume::<u32>(), 14);
+/// assert!(left.contains(&[5, 2]) && !left.contains(&[5, 3]));
+/// ```
+#[derive(Clone, Debug)]
+pub struct BoxSet<T, const N: usize> {
+    /// Pairwise disjoint and non-empty
+    cuboids: Vec<Cuboid<T, N>>,
+}
+
+impl<T: Endpoint, const N: usize> BoxSet<T, N> {
+    pub fn new() -> BoxSet<T, N> {
+        BoxSet { cuboids: Vec::new() }
+    }
+
+    pub fn cuboids(&self) -> &[Cuboid<T, N>] {
+        &self.cuboids
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cuboids.is_empty()
+    }
+
+    pub fn contains(&self, point: &[T; N]) -> bool {
+        self.cuboids.iter().any(|cuboid| cuboid.contains(point))
+    }
+
+    pub fn volume<V: From<T> + Product + Sum>(&self) -> V {
+        self.cuboids.iter().map(Cuboid::volume::<V>).sum()
+    }
+
+    /// Add a cuboid, keeping only the parts that are not in the set yet
+    pub fn insert(&mut self, cuboid: Cuboid<T, N>) {
+        let new_parts = BoxSet::from(cuboid).difference(self);
+        self.cuboids.extend(new_parts.cuboids);
+    }
+
+    pub fn union(&self, other: &BoxSet<T, N>) -> BoxSet<T, N> {
+        let mut union = self.clone();
+        union.extend(other.cuboids.iter().cloned());
+        union
+    }
+
+    pub fn intersection(&self, other: &BoxSet<T, N>) -> BoxSet<T, N> {
+        let cuboids = self.cuboids.iter()
+            .flat_map(|a| other.cuboids.iter().filter(|b| !a.is_disjoint(b)).map(move |b| a.intersection(b)))
+            .collect();
+        BoxSet { cuboids }
+    }
+
+    pub fn difference(&self, other: &BoxSet<T, N>) -> BoxSet<T, N> {
+        let cuboids = self.cuboids.iter().flat_map(|cuboid| {
+            other.cuboids.iter().fold(vec![cuboid.clone()], |pieces, removed| {
+                if pieces.iter().all(|piece| piece.is_disjoint(removed)) {
+                    return pieces;
+                }
+                pieces.iter().flat_map(|piece| piece.difference(removed)).collect()
+            })
+        }).collect();
+        BoxSet { cuboids }
+    }
+
+    /// Split the set into the points whose coordinate along `axis` is in `values`, and the other points.
+    pub fn partition(&self, axis: usize, values: &IntervalSet<T>) -> (BoxSet<T, N>, BoxSet<T, N>) {
+        let restrict = |extent: fn(&IntervalSet<T>, &IntervalSet<T>) -> IntervalSet<T>| {
+            let cuboids = self.cuboids.iter().filter_map(|cuboid| {
+                let mut piece = cuboid.clone();
+                piece.axes[axis] = extent(&cuboid.axes[axis], values);
+                (!piece.axes[axis].is_empty()).then_some(piece)
+            }).collect();
+            BoxSet { cuboids }
+        };
+        (restrict(IntervalSet::intersection), restrict(IntervalSet::difference))
+    }
+}
+
+impl<T: Endpoint, const N: usize> Default for BoxSet<T, N> {
+    fn default() -> Self {
+        BoxSet::new()
+    }
+}
+
+/// Insert every cuboid, like [`BoxSet::insert`]
+impl<T: Endpoint, const N: usize> Extend<Cuboid<T, N>> for BoxSet<T, N> {
+    fn extend<I: IntoIterator<Item = Cuboid<T, N>>>(&mut self, iter: I) {
+        for cuboid in iter {
+            self.insert(cuboid);
+        }
+    }
+}
+
+impl<T, const N: usize> IntoIterator for BoxSet<T, N> {
+    type Item = Cuboid<T, N>;
+    type IntoIter = std::vec::IntoIter<Cuboid<T, N>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cuboids.into_iter()
+    }
+}
+
+impl<T: Endpoint, const N: usize> From<Cuboid<T, N>> for BoxSet<T, N> {
+    fn from(cuboid: Cuboid<T, N>) -> Self {
+        let cuboids = if cuboid.is_empty() { vec![] } else { vec![cuboid] };
+        BoxSet { cuboids }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::aux::rng::Rng;
+    use super::*;
+
+    // Property tests: random sets over a small universe, checked against the same operations on bitsets.
+    // Every integer (or point) in the universe corresponds to one bit of a `u64`.
+
+    const SIDE: i32 = 4;
+    const N_CASES: usize = 500;
+
+    fn below(rng: &mut Rng, n: i32) -> i32 {
+        rng.below(n as usize) as i32
+    }
+
+    fn random_range(rng: &mut Rng, n: i32) -> Range<i32> {
+        let a = below(rng, n + 1);
+        let b = below(rng, n + 1);
+        a.min(b)..a.max(b)
+    }
+
+    fn random_interval_set(rng: &mut Rng) -> IntervalSet<i32> {
+        let n_ranges = below(rng, 5);
+        (0..n_ranges).map(|_| random_range(rng, 64)).collect()
+    }
+
+    fn random_box_set(rng: &mut Rng) -> BoxSet<i32, 3> {
+        let mut set = BoxSet::new();
+        for _ in 0..below(rng, 5) {
+            set.insert(Cuboid::new(from_fn(|_| (0..below(rng, 3)).map(|_| random_range(rng, SIDE)).collect())));
+        }
+        set
+    }
+
+    fn interval_bits(set: &IntervalSet<i32>) -> u64 {
+        (0..64).filter(|&i| set.contains(&i)).fold(0, |bits, i| bits | 1 << i)
+    }
+
+    fn box_bits(set: &BoxSet<i32, 3>) -> u64 {
+        (0..64).filter(|&i| set.contains(&[i / 16, i / 4 % 4, i % 4])).fold(0, |bits, i| bits | 1 << i)
+    }
+
+    fn assert_normalized(set: &IntervalSet<i32>) {
+        assert!(set.ranges().iter().all(|range| !range.is_empty()), "{:?}", set);
+        assert!(set.ranges().windows(2).all(|pair| pair[0].end < pair[1].start), "{:?}", set);
+    }
+
+    fn assert_disjoint(set: &BoxSet<i32, 3>) {
+        assert!(set.cuboids().iter().all(|cuboid| !cuboid.is_empty()), "{:?}", set);
+        let sum: i64 = set.cuboids().iter().map(Cuboid::volume::<i64>).sum();
+        assert_eq!(sum, box_bits(set).count_ones() as i64, "{:?}", set);
+    }
+
+    #[test]
+    fn test_interval_set_properties() {
+        let mut rng = Rng(0x2545f4914f6cdd1d);
+        for _ in 0..N_CASES {
+            let (a, b) = (random_interval_set(&mut rng), random_interval_set(&mut rng));
+            let (a_bits, b_bits) = (interval_bits(&a), interval_bits(&b));
+            assert_normalized(&a);
+            assert_eq!(a.len(), a_bits.count_ones() as i32);
+            assert_eq!(a.min(), (a_bits != 0).then(|| a_bits.trailing_zeros() as i32));
+            assert_eq!(a.max(), (a_bits != 0).then(|| 63 - a_bits.leading_zeros() as i32));
+            for (result, expected) in [
+                (a.union(&b), a_bits | b_bits),
+                (a.intersection(&b), a_bits & b_bits),
+                (a.difference(&b), a_bits & !b_bits),
+                (a.complement(0..64), !a_bits),
+            ] {
+                assert_normalized(&result);
+                assert_eq!(interval_bits(&result), expected, "{:?} {:?} {:?}", a, b, result);
+            }
+            assert_eq!(a.is_disjoint(&b), a_bits & b_bits == 0);
+            let mut inserted = a.clone();
+            let range = random_range(&mut rng, 64);
+            inserted.insert(range.clone());
+            assert_eq!(inserted, a.union(&IntervalSet::from(range)));
+        }
+    }
+
+    #[test]
+    fn test_box_set_properties() {
+        let mut rng = Rng(0x9e3779b97f4a7c15);
+        for _ in 0..N_CASES {
+            let (a, b) = (random_box_set(&mut rng), random_box_set(&mut rng));
+            let (a_bits, b_bits) = (box_bits(&a), box_bits(&b));
+            assert_disjoint(&a);
+            assert_eq!(a.volume::<i64>(), a_bits.count_ones() as i64);
+            for (result, expected) in [
+                (a.union(&b), a_bits | b_bits),
+                (a.intersection(&b), a_bits & b_bits),
+                (a.difference(&b), a_bits & !b_bits),
+            ] {
+                assert_disjoint(&result);
+                assert_eq!(box_bits(&result), expected, "{:?} {:?} {:?}", a, b, result);
+            }
+            let axis = rng.below(3);
+            let values = random_interval_set(&mut rng);
+            let (inside, outside) = a.partition(axis, &values);
+            assert_disjoint(&inside);
+            assert_disjoint(&outside);
+            let in_values = (0..64)
+                .filter(|&i| values.contains(&[i / 16, i / 4 % 4, i % 4][axis]))
+                .fold(0u64, |bits, i| bits | 1 << i);
+            assert_eq!(box_bits(&inside), a_bits & in_values);
+            assert_eq!(box_bits(&outside), a_bits & !in_values);
+        }
+    }
+
+    #[test]
+    fn test_from_inclusive() {
+        assert_eq!(IntervalSet::from_inclusive(2..=4), IntervalSet::from(2..5));
+        let (start, end) = (3, 2);
+        assert!(IntervalSet::from_inclusive(start..=end).is_empty());
+    }
+}
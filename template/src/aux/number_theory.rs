@@ -0,0 +1,167 @@
+// Integer arithmetic for modular puzzles. Inputs are `i64`, intermediate products are computed in `i128` so that they don't overflow.
+
+
+/// The extended Euclidean algorithm: `(g, x, y)` such that `g = gcd(a, b) >= 0` and `a * x + b * y = g`.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::number_theory::ext_gcd;
+///
+/// let (g, x, y) = ext_gcd(240, 46);
+/// assert_eq!(g, 2);
+/// assert_eq!(240 * x + 46 * y, 2);
+/// ```
+pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut r_prev, mut r) = (a, b);
+    let (mut s_prev, mut s) = (1, 0);
+    let (mut t_prev, mut t) = (0, 1);
+    while r != 0 {
+        let quotient = r_prev / r;
+        (r_prev, r) = (r, r_prev - quotient * r);
+        (s_prev, s) = (s, s_prev - quotient * s);
+        (t_prev, t) = (t, t_prev - quotient * t);
+    }
+    if r_prev < 0 {
+        (-r_prev, -s_prev, -t_prev)
+    } else {
+        (r_prev, s_prev, t_prev)
+    }
+}
+
+pub fn gcd(a: i64, b: i64) -> i64 {
+    ext_gcd(a, b).0
+}
+
+/// The least common multiple of `a` and `b`, which is never negative.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::number_theory::lcm;
+///
+/// assert_eq!(lcm(4, 6), 12);
+/// assert_eq!([2, 3, 4].into_iter().fold(1, lcm), 12);
+/// ```
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+/// The inverse of `a` modulo `modulus`, in `0..modulus`, or `None` if `a` and `modulus` are not coprime.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::number_theory::mod_inverse;
+///
+/// assert_eq!(mod_inverse(3, 7), Some(5));
+/// assert_eq!(mod_inverse(4, 6), None);
+/// ```
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    let (g, x, _) = ext_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Whether `n` is prime, by trial division.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::number_theory::is_prime;
+///
+/// assert!(is_prime(277));
+/// assert!(!is_prime(1));
+/// assert!(!is_prime(43 * 47));
+/// ```
+pub fn is_prime(n: i64) -> bool {
+    n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| n % d != 0)
+}
+
+/// Solve a system of linear congruences `x ≡ remainder (mod modulus)` with the Chinese remainder theorem.
+///
+/// The moduli need not be coprime. Returns `(x, m)` such that the solutions are exactly the `x + k * m`,
+/// where `m` is the least common multiple of the moduli and `0 <= x < m`, or `None` if the congruences contradict each other.
+///
+/// # Panics
+///
+/// Panics if a modulus is not positive, or if the least common multiple of the moduli doesn't fit in an `i64`.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::number_theory::crt;
+///
+/// assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// // Non-coprime moduli
+/// assert_eq!(crt(&[(3, 4), (5, 6)]), Some((11, 12)));
+/// assert_eq!(crt(&[(3, 4), (4, 6)]), None);
+/// ```
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    congruences.iter().try_fold((0, 1), |(x, m), &(remainder, modulus)| {
+        assert!(modulus > 0, "Modulus must be positive, got {}", modulus);
+        // x + m * k ≡ remainder (mod modulus), so m * k ≡ remainder - x, which is solvable iff g divides the right-hand side
+        let (g, m_inverse, _) = ext_gcd(m, modulus);
+        let difference = remainder - x;
+        if difference % g != 0 {
+            return None;
+        }
+        let reduced_modulus = modulus / g;
+        let k = (difference / g) as i128 * m_inverse as i128 % reduced_modulus as i128;
+        let new_m = m as i128 * reduced_modulus as i128;
+        let new_x = (x as i128 + m as i128 * k).rem_euclid(new_m);
+        let new_m = i64::try_from(new_m).expect("Least common multiple of the moduli overflows");
+        Some((new_x as i64, new_m))
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ext_gcd() {
+        for a in -30..30 {
+            for b in -30..30 {
+                let (g, x, y) = ext_gcd(a, b);
+                assert_eq!(a * x + b * y, g);
+                assert!(g >= 0);
+                if g != 0 {
+                    assert_eq!((a % g, b % g), (0, 0));
+                }
+                let brute_force = (1..=30).rev().find(|d| a % d == 0 && b % d == 0).unwrap();
+                assert_eq!(g, if (a, b) == (0, 0) { 0 } else { brute_force });
+            }
+        }
+    }
+
+    #[test]
+    fn test_crt_brute_force() {
+        for n1 in 1..12i64 {
+            for n2 in 1..12 {
+                for a1 in 0..n1 {
+                    for a2 in -3..n2 {
+                        let brute_force = (0..n1 * n2).find(|x| x % n1 == a1 && (x - a2).rem_euclid(n2) == 0);
+                        let solution = crt(&[(a1, n1), (a2, n2)]);
+                        assert_eq!(solution.map(|(x, _)| x), brute_force);
+                        if let Some((_, m)) = solution {
+                            assert_eq!(m, lcm(n1, n2));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_crt_large() {
+        let moduli = [277 * 43, 277 * 47, 277 * 53, 277 * 59, 277 * 61, 277 * 67];
+        let congruences = moduli.map(|m| (m - 277, m));
+        let (x, m) = crt(&congruences).unwrap();
+        assert_eq!(m, 277 * 43 * 47 * 53 * 59 * 61 * 67);
+        assert_eq!(x, m - 277);
+    }
+}
@@ -0,0 +1,192 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+
+pub use num_bigint::BigInt;
+use num_bigint::Sign;
+
+
+// Integer types that solvers can be generic over, so that the caller picks the trade-off between speed and range:
+// `i64` or `i128` are fast but wrap silently in release builds, `Checked` panics on overflow, and `BigInt` is exact.
+
+
+/// An integer type to compute with.
+pub trait Integer:
+    Clone + Ord + fmt::Debug + fmt::Display + From<i64>
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self>
+{
+    fn zero() -> Self {
+        Self::from(0)
+    }
+
+    fn one() -> Self {
+        Self::from(1)
+    }
+
+    /// The absolute difference of `self` and `other`, computed without negating anything.
+    fn distance(self, other: Self) -> Self {
+        if self >= other { self - other } else { other - self }
+    }
+}
+
+impl<T> Integer for T
+where
+    T: Clone + Ord + fmt::Debug + fmt::Display + From<i64>
+        + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>
+{}
+
+/// The sum of `values`, in any [`Integer`] type.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::numeric::{sum, BigInt, Checked};
+///
+/// assert_eq!(sum([1, 2, 3].map(Checked::<i64>)), Checked(6));
+/// assert_eq!(sum([i64::MAX, i64::MAX].map(BigInt::from)), BigInt::from(i64::MAX) * BigInt::from(2));
+/// ```
+pub fn sum<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::zero(), |total, value| total + value)
+}
+
+/// Convert a `BigInt` into any [`Integer`] type, building it from 32-bit digits in that type,
+/// so that it is exact if the value fits (and panics for `Checked` if it does not).
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::numeric::{from_big, BigInt, Checked};
+///
+/// let big = BigInt::from(-3) * BigInt::from(2).pow(100);
+/// assert_eq!(from_big::<Checked<i128>>(&big), Checked(-3 << 100));
+/// assert_eq!(from_big::<BigInt>(&big), big);
+/// assert_eq!(from_big::<i64>(&BigInt::from(-42)), -42);
+/// ```
+///
+/// ```should_panic
+/// use aoc::aux::numeric::{from_big, BigInt, Checked};
+///
+/// from_big::<Checked<i64>>(&BigInt::from(2).pow(64));
+/// ```
+pub fn from_big<T: Integer>(value: &BigInt) -> T {
+    if let Ok(value) = i64::try_from(value) {
+        return T::from(value);
+    }
+    let (sign, digits) = value.to_u32_digits();
+    let shift = T::from(1 << 32);
+    // Accumulate towards the sign of the value, so that the most negative value of `T` does not overflow
+    digits.iter().rev().fold(T::zero(), |total, &digit| match sign {
+        Sign::Minus => total * shift.clone() - T::from(digit as i64),
+        _ => total * shift.clone() + T::from(digit as i64),
+    })
+}
+
+
+/// A primitive integer whose arithmetic panics on overflow, also in release builds.
+///
+/// # Example
+///
+/// ```should_panic
+/// use aoc::aux::numeric::Checked;
+///
+/// let _ = Checked(i64::MAX) + Checked(1);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checked<T>(pub T);
+
+macro_rules! impl_checked {
+    ($($t:ty),*) => {$(
+        impl From<i64> for Checked<$t> {
+            fn from(value: i64) -> Self {
+                Checked(value.into())
+            }
+        }
+
+        impl Add for Checked<$t> {
+            type Output = Self;
+            fn add(self, other: Self) -> Self {
+                Checked(self.0.checked_add(other.0).unwrap_or_else(|| panic!("Overflow in {} + {}", self.0, other.0)))
+            }
+        }
+
+        impl Sub for Checked<$t> {
+            type Output = Self;
+            fn sub(self, other: Self) -> Self {
+                Checked(self.0.checked_sub(other.0).unwrap_or_else(|| panic!("Overflow in {} - {}", self.0, other.0)))
+            }
+        }
+
+        impl Mul for Checked<$t> {
+            type Output = Self;
+            fn mul(self, other: Self) -> Self {
+                Checked(self.0.checked_mul(other.0).unwrap_or_else(|| panic!("Overflow in {} * {}", self.0, other.0)))
+            }
+        }
+
+        impl Div for Checked<$t> {
+            type Output = Self;
+            fn div(self, other: Self) -> Self {
+                Checked(self.0.checked_div(other.0).unwrap_or_else(|| panic!("Overflow in {} / {}", self.0, other.0)))
+            }
+        }
+    )*};
+}
+
+impl_checked!(i64, i128);
+
+impl<T: fmt::Display> fmt::Display for Checked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle<T: Integer>(n: i64) -> T {
+        T::from(n) * (T::from(n) + T::one()) / T::from(2)
+    }
+
+    #[test]
+    fn test_types_agree() {
+        for n in [0, 1, 7, 1_000_000] {
+            let expected = n * (n + 1) / 2;
+            assert_eq!(triangle::<i64>(n), expected);
+            assert_eq!(triangle::<i128>(n), expected as i128);
+            assert_eq!(triangle::<Checked<i64>>(n), Checked(expected));
+            assert_eq!(triangle::<BigInt>(n), BigInt::from(expected));
+        }
+        assert_eq!(Checked::<i128>::from(-5).distance(Checked(3)), Checked(8));
+        assert_eq!(BigInt::from(3).distance(BigInt::from(-5)), BigInt::from(8));
+    }
+
+    #[test]
+    fn test_big() {
+        let n = 5_000_000_000;
+        assert_eq!(triangle::<BigInt>(n).to_string(), "12500000002500000000");
+        assert_eq!(triangle::<Checked<i128>>(n), Checked(12500000002500000000));
+    }
+
+    #[test]
+    fn test_from_big() {
+        for value in [0, -1, i64::MIN as i128 - 1, 1 << 64, -(1 << 64) + 7, i128::MAX, i128::MIN, 0x1234_5678_9ABC_DEF0_1357_9BDF] {
+            let big = BigInt::from(value);
+            assert_eq!(from_big::<i128>(&big), value);
+            assert_eq!(from_big::<Checked<i128>>(&big), Checked(value));
+            assert_eq!(from_big::<BigInt>(&big), big);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Overflow")]
+    fn test_from_big_overflow() {
+        from_big::<Checked<i128>>(&(BigInt::from(i128::MAX) + BigInt::from(1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Overflow in 5000000000 * 5000000001")]
+    fn test_checked_overflow() {
+        triangle::<Checked<i64>>(5_000_000_000);
+    }
+}
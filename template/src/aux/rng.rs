@@ -0,0 +1,30 @@
+// Shared by the randomized tests of the library and the days, which check solvers against brute force on
+// generated inputs. Hidden from the docs, since it is not meant for anything else.
+
+
+/// A xorshift generator, good enough to produce test cases.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::rng::Rng;
+///
+/// let mut rng = Rng(0x2545F4914F6CDD1D);
+/// assert!((0..100).all(|_| rng.below(6) < 6));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Rng(pub u64);
+
+impl Rng {
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number in `0..n`, slightly biased towards the low end unless `n` is a power of two.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
@@ -0,0 +1,338 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+
+// Shortest-path searches over implicit graphs. A graph is given by a successor function mapping a node to its neighbours
+// (with the cost of each edge for the weighted searches), so the nodes never need to be enumerated up front.
+// Every search returns a `SearchTree`, from which the cost of and path to any settled node can be read back.
+
+
+/// Counters describing how much work a search did.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// Nodes taken off the queue and expanded (each node is expanded at most once)
+    pub visited: usize,
+    /// Distinct nodes that were reached, whether or not they were expanded
+    pub discovered: usize,
+}
+
+/// The cost of and path to a goal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchResult<N, C> {
+    pub cost: C,
+    /// All nodes from the start to the goal, both included
+    pub path: Vec<N>,
+    pub stats: SearchStats,
+}
+
+/// Everything a search learned: the cheapest known cost and predecessor of every reached node, and the goal if one was found.
+///
+/// Costs and paths are optimal for visited nodes. A search stops as soon as it visits a goal,
+/// so only nodes that were visited before it are guaranteed to have their final cost.
+#[derive(Clone, Debug)]
+pub struct SearchTree<N, C> {
+    nodes: HashMap<N, NodeInfo<N, C>>,
+    pub goal: Option<N>,
+    pub stats: SearchStats,
+}
+
+#[derive(Clone, Debug)]
+struct NodeInfo<N, C> {
+    cost: C,
+    parent: Option<N>,
+    visited: bool,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> SearchTree<N, C> {
+    fn new() -> SearchTree<N, C> {
+        SearchTree { nodes: HashMap::new(), goal: None, stats: SearchStats::default() }
+    }
+
+    /// The cheapest known cost of reaching `node`, if it was reached at all.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.nodes.get(node).map(|info| info.cost)
+    }
+
+    pub fn is_visited(&self, node: &N) -> bool {
+        self.nodes.get(node).is_some_and(|info| info.visited)
+    }
+
+    /// The cheapest known path from a start to `node`, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        let mut current = self.nodes.get(node)?;
+        let mut path = vec![node.clone()];
+        while let Some(parent) = &current.parent {
+            path.push(parent.clone());
+            current = &self.nodes[parent];
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// The cost of and path to the goal, or `None` if no goal was reached.
+    pub fn result(&self) -> Option<SearchResult<N, C>> {
+        let goal = self.goal.as_ref()?;
+        Some(SearchResult {
+            cost: self.cost(goal)?,
+            path: self.path_to(goal)?,
+            stats: self.stats,
+        })
+    }
+
+    /// Record a tentative cost for `node`, returning whether it improved on the previous one.
+    fn relax(&mut self, node: &N, cost: C, parent: Option<&N>) -> bool where C: Ord {
+        match self.nodes.entry(node.clone()) {
+            Entry::Occupied(mut entry) => {
+                let info = entry.get_mut();
+                if info.visited || info.cost <= cost {
+                    return false;
+                }
+                info.cost = cost;
+                info.parent = parent.cloned();
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(NodeInfo { cost, parent: parent.cloned(), visited: false });
+                self.stats.discovered += 1;
+            }
+        }
+        true
+    }
+}
+
+
+/// A goal predicate that never matches, to explore everything reachable from the starts.
+pub fn no_goal<N>(_: &N) -> bool {
+    false
+}
+
+/// Dijkstra's algorithm: the cheapest path from any of `starts` to the first node satisfying `is_goal`.
+///
+/// Edge costs must be non-negative; `C::default()` is the cost of a start node.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::search::dijkstra;
+///
+/// // From n, go to n + 1 for a cost of 1 or to 2n for a cost of 3
+/// let successors = |&n: &u32| [(n + 1, 1), (2 * n, 3)];
+/// let tree = dijkstra([1], successors, |&n| n == 10);
+/// let result = tree.result().unwrap();
+/// assert_eq!(result.cost, 7);
+/// assert_eq!(result.path, vec![1, 2, 3, 4, 5, 10]);
+/// ```
+pub fn dijkstra<N, C, S, I>(starts: impl IntoIterator<Item = N>, successors: S, is_goal: impl Fn(&N) -> bool) -> SearchTree<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    S: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, successors, |_| C::default(), is_goal)
+}
+
+/// A* search: like [`dijkstra`], but nodes are prioritised by their cost plus `heuristic`, an estimate of the remaining cost.
+///
+/// The heuristic must never overestimate the remaining cost and must be consistent
+/// (`heuristic(a) <= cost(a, b) + heuristic(b)` for every edge), otherwise the result may not be optimal.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::search::{astar, dijkstra};
+///
+/// // Walk on the integers towards 100
+/// let successors = |&n: &i32| [(n - 1, 1), (n + 1, 1)];
+/// let is_goal = |&n: &i32| n == 100;
+/// let informed = astar([0], successors, |&n| (100 - n).abs(), is_goal);
+/// let uninformed = dijkstra([0], successors, is_goal);
+/// assert_eq!(informed.result().unwrap().cost, 100);
+/// assert_eq!(uninformed.result().unwrap().cost, 100);
+/// assert!(informed.stats.visited < uninformed.stats.visited);
+/// ```
+pub fn astar<N, C, S, I, H>(starts: impl IntoIterator<Item = N>, successors: S, heuristic: H, is_goal: impl Fn(&N) -> bool) -> SearchTree<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    S: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: Fn(&N) -> C,
+{
+    let mut tree = SearchTree::new();
+    let mut fringe = BinaryHeap::new();
+    let mut n_pushed = 0;
+    for start in starts {
+        if tree.relax(&start, C::default(), None) {
+            fringe.push(HeapEntry { priority: heuristic(&start), cost: C::default(), order: n_pushed, node: start });
+            n_pushed += 1;
+        }
+    }
+    while let Some(HeapEntry { cost, node, .. }) = fringe.pop() {
+        let info = tree.nodes.get_mut(&node).unwrap();
+        if info.visited || info.cost < cost {
+            continue;  // stale entry, the node was reached more cheaply since it was pushed
+        }
+        info.visited = true;
+        tree.stats.visited += 1;
+        if is_goal(&node) {
+            tree.goal = Some(node);
+            break;
+        }
+        for (neighbour, edge_cost) in successors(&node) {
+            let new_cost = cost + edge_cost;
+            if tree.relax(&neighbour, new_cost, Some(&node)) {
+                fringe.push(HeapEntry { priority: new_cost + heuristic(&neighbour), cost: new_cost, order: n_pushed, node: neighbour });
+                n_pushed += 1;
+            }
+        }
+    }
+    tree
+}
+
+/// Breadth-first search: the path with the fewest edges from any of `starts` to the first node satisfying `is_goal`.
+///
+/// # Example
+///
+/// ```
+/// use aoc::aux::search::{bfs, no_goal};
+///
+/// let successors = |&n: &u32| [(n * 2) % 7, (n + 3) % 7];
+/// let tree = bfs([0], successors, |&n| n == 5);
+/// assert_eq!(tree.result().unwrap().path, vec![0, 3, 6, 5]);
+///
+/// let tree = bfs([1], successors, no_goal);
+/// assert_eq!(tree.stats.visited, 7);
+/// assert_eq!(tree.cost(&0), Some(2));
+/// ```
+pub fn bfs<N, S, I>(starts: impl IntoIterator<Item = N>, successors: S, is_goal: impl Fn(&N) -> bool) -> SearchTree<N, usize>
+where
+    N: Clone + Eq + Hash,
+    S: Fn(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut tree = SearchTree::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if tree.relax(&start, 0, None) {
+            queue.push_back(start);
+        }
+    }
+    while let Some(node) = queue.pop_front() {
+        let info = tree.nodes.get_mut(&node).unwrap();
+        info.visited = true;
+        let cost = info.cost;
+        tree.stats.visited += 1;
+        if is_goal(&node) {
+            tree.goal = Some(node);
+            break;
+        }
+        for neighbour in successors(&node) {
+            // The first time a node is reached is along a shortest path, so only new nodes are queued
+            if !tree.nodes.contains_key(&neighbour) && tree.relax(&neighbour, cost + 1, Some(&node)) {
+                queue.push_back(neighbour);
+            }
+        }
+    }
+    tree
+}
+
+
+// Min-heap entry ordered by priority. Ties are broken by insertion order, so that searches are deterministic.
+struct HeapEntry<N, C> {
+    priority: C,
+    cost: C,
+    order: usize,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for HeapEntry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, C: Ord> Eq for HeapEntry<N, C> {}
+
+impl<N, C: Ord> PartialOrd for HeapEntry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for HeapEntry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+            .then(other.order.cmp(&self.order))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aux::Grid;
+
+    const MAZE: &str = "\
+..#....
+.##.##.
+...#...
+.#...#.
+.#.#.#.";
+
+    fn maze() -> Grid<bool> {
+        Grid::parse(MAZE, "`.` or `#`", |c| match c {
+            '.' => Some(true),
+            '#' => Some(false),
+            _ => None,
+        }).unwrap()
+    }
+
+    #[test]
+    fn test_searches_agree() {
+        let maze = maze();
+        let goal = (0, 6);
+        let neighbours = |&index: &(usize, usize)| maze.neighbours4(index).filter(|&other| maze[other]).collect::<Vec<_>>();
+        let weighted = |index: &(usize, usize)| neighbours(index).into_iter().map(|other| (other, 1));
+        let manhattan = |&(y, x): &(usize, usize)| y.abs_diff(goal.0) + x.abs_diff(goal.1);
+
+        let by_bfs = bfs([(0, 0)], neighbours, |&index| index == goal).result().unwrap();
+        let by_dijkstra = dijkstra([(0, 0)], weighted, |&index| index == goal).result().unwrap();
+        let by_astar = astar([(0, 0)], weighted, manhattan, |&index| index == goal).result().unwrap();
+        assert_eq!(by_bfs.cost, 12);
+        assert_eq!(by_dijkstra.cost, 12);
+        assert_eq!(by_astar.cost, 12);
+        for result in [&by_bfs, &by_dijkstra, &by_astar] {
+            assert_eq!(result.path.len(), 13);
+            assert_eq!(result.path.first(), Some(&(0, 0)));
+            assert_eq!(result.path.last(), Some(&goal));
+            assert!(result.path.windows(2).all(|step| neighbours(&step[0]).contains(&step[1])));
+        }
+        assert!(by_astar.stats.visited <= by_dijkstra.stats.visited);
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let maze = maze();
+        let neighbours = |&index: &(usize, usize)| maze.neighbours4(index).filter(|&other| maze[other]).collect::<Vec<_>>();
+        let tree = bfs([(0, 0)], neighbours, |&index| index == (0, 2));
+        assert_eq!(tree.goal, None);
+        assert_eq!(tree.result(), None);
+        let n_open = maze.iter().filter(|&&open| open).count();
+        assert_eq!(tree.stats.visited, n_open);
+        assert_eq!(tree.stats.discovered, n_open);
+    }
+
+    #[test]
+    fn test_multiple_starts() {
+        let successors = |&n: &i32| [(n + 1, 2), (n - 1, 2)];
+        let tree = dijkstra([0, 10], successors, |&n| n == 7);
+        let result = tree.result().unwrap();
+        assert_eq!(result.cost, 6);
+        assert_eq!(result.path, vec![10, 9, 8, 7]);
+        assert_eq!(tree.cost(&1), Some(2));
+        assert_eq!(tree.path_to(&1), Some(vec![0, 1]));
+    }
+}
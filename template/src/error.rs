@@ -0,0 +1,167 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+
+/// An error produced while parsing a puzzle input, pointing at the exact place where parsing broke.
+///
+/// Lines and columns are 1-based, columns count characters (not bytes).
+/// An empty `snippet` means that the input ended where `expected` was expected.
+///
+/// Parsers that only see part of the input (a line, a block, a token) report positions relative to that part;
+/// the caller then moves the error into its own coordinates with [`ParseError::within`].
+///
+/// # Example
+///
+/// ```
+/// use aoc::error::ParseError;
+///
+/// let input = "1 2\n3 x 5";
+/// let token = input.split_whitespace().find(|token| token.parse::<u32>().is_err()).unwrap();
+/// let err = ParseError::at(input, token, "a number");
+/// assert_eq!((err.line, err.column), (2, 3));
+/// assert_eq!(err.to_string(), "line 2, column 3: expected a number, found `x`");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, snippet: impl Into<String>, expected: impl Into<String>) -> ParseError {
+        ParseError { line, column, snippet: snippet.into(), expected: expected.into() }
+    }
+
+    /// Create an error for `snippet`, which must be a subslice of `source`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `snippet` does not point into `source`.
+    pub fn at(source: &str, snippet: &str, expected: impl Into<String>) -> ParseError {
+        let (line, column) = locate(source, snippet);
+        ParseError::new(line, column, snippet, expected)
+    }
+
+    /// Create an error for the single character starting at byte `index` of `source`.
+    pub fn at_char(source: &str, index: usize, expected: impl Into<String>) -> ParseError {
+        let len = source[index..].chars().next().map_or(0, char::len_utf8);
+        ParseError::at(source, &source[index..index + len], expected)
+    }
+
+    /// Create an error for a `source` that ended before `expected` was found.
+    pub fn end_of(source: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::at(source, &source[source.len()..], expected)
+    }
+
+    /// Move an error reported relative to `part` into the coordinates of `source`, of which `part` is a subslice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aoc::error::ParseError;
+    ///
+    /// let input = "ab\ncd?f";
+    /// let line = input.lines().nth(1).unwrap();
+    /// let err = ParseError::at_char(line, 2, "a letter");
+    /// assert_eq!((err.line, err.column), (1, 3));
+    /// assert_eq!(err.within(input, line), ParseError::new(2, 3, "?", "a letter"));
+    /// ```
+    pub fn within(self, source: &str, part: &str) -> ParseError {
+        let (line, column) = locate(source, part);
+        ParseError {
+            line: line + self.line - 1,
+            column: if self.line == 1 { column + self.column - 1 } else { self.column },
+            ..self
+        }
+    }
+
+    /// Shift the error down by `offset` lines, for parsers that are handed lines one at a time.
+    pub fn with_line_offset(self, offset: usize) -> ParseError {
+        ParseError { line: self.line + offset, ..self }
+    }
+}
+
+/// Parse `token`, a subslice of `source`, reporting a failure as a [`ParseError`] pointing at the token.
+///
+/// # Example
+///
+/// ```
+/// use aoc::error::parse_at;
+///
+/// let line = "x=12, y=a4";
+/// let (x, y) = (&line[2..4], &line[8..]);
+/// assert_eq!(parse_at::<u32>(line, x, "a number"), Ok(12));
+/// assert_eq!(parse_at::<u32>(line, y, "a number").unwrap_err().column, 9);
+/// ```
+pub fn parse_at<T: FromStr>(source: &str, token: &str, expected: impl Into<String>) -> Result<T, ParseError> {
+    token.parse().map_err(|_| ParseError::at(source, token, expected))
+}
+
+/// Find the 1-based line and column at which `snippet` starts within `source`.
+fn locate(source: &str, snippet: &str) -> (usize, usize) {
+    let offset = (snippet.as_ptr() as usize).wrapping_sub(source.as_ptr() as usize);
+    let end = offset.checked_add(snippet.len());
+    assert!(end.is_some_and(|end| end <= source.len()), "Snippet is not part of the source");
+    let prefix = &source[..offset];
+    let line_start = prefix.rfind('\n').map_or(0, |i| i + 1);
+    let line = prefix.matches('\n').count() + 1;
+    let column = prefix[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: expected {}, ", self.line, self.column, self.expected)?;
+        match self.snippet.as_str() {
+            "" => write!(f, "found end of input"),
+            snippet => write!(f, "found `{}`", snippet),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate() {
+        let source = "abc\nde\n\nfgh";
+        assert_eq!(locate(source, &source[0..1]), (1, 1));
+        assert_eq!(locate(source, &source[2..3]), (1, 3));
+        assert_eq!(locate(source, &source[4..6]), (2, 1));
+        assert_eq!(locate(source, &source[7..7]), (3, 1));
+        assert_eq!(locate(source, &source[10..]), (4, 3));
+        assert_eq!(locate(source, &source[11..]), (4, 4));
+    }
+
+    #[test]
+    fn test_within() {
+        let source = "header\n\nab\ncd\n";
+        let block = &source[8..];
+        let err = ParseError::at(block, &block[4..5], "a digit");
+        assert_eq!((err.line, err.column), (2, 2));
+        let err = err.within(source, block);
+        assert_eq!((err.line, err.column), (4, 2));
+        assert_eq!(err.snippet, "d");
+    }
+
+    #[test]
+    fn test_display() {
+        let err = ParseError::end_of("1,2,", "a number");
+        assert_eq!(err.to_string(), "line 1, column 5: expected a number, found end of input");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_foreign_snippet() {
+        let other = String::from("abc");
+        ParseError::at("abc", &other, "anything");
+    }
+}
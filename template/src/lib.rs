@@ -1,2 +1,9 @@
+#![cfg_attr(test, feature(test))]
+
+#[cfg(test)]
+extern crate test;
+
+pub mod aux;
+pub mod error;
+pub mod puzzle;
 pub mod today;
-mod aux;
@@ -0,0 +1,69 @@
+use std::fmt;
+use std::path::Path;
+
+use crate::aux::read_input;
+
+
+/// The contract every day implements: parse the raw input once, then solve either part on the parsed input.
+///
+/// Implementors are unit structs (e.g. `day17::Day17`), so generic tooling can be written as `fn foo<P: Puzzle>()`.
+///
+/// # Example
+///
+/// ```
+/// use aoc::puzzle::Puzzle;
+///
+/// struct Sum;
+///
+/// impl Puzzle for Sum {
+///     type Input = Vec<u64>;
+///     type Output1 = u64;
+///     type Output2 = u64;
+///     type ParseError = std::num::ParseIntError;
+///
+///     fn parse(input: &str) -> Result<Self::Input, Self::ParseError> {
+///         input.lines().map(str::parse).collect()
+///     }
+///
+///     fn part1(input: &Self::Input) -> Self::Output1 {
+///         input.iter().sum()
+///     }
+///
+///     fn part2(input: &Self::Input) -> Self::Output2 {
+///         input.iter().product()
+///     }
+/// }
+///
+/// let input = Sum::parse("2\n3\n4").unwrap();
+/// assert_eq!(Sum::part1(&input), 9);
+/// assert_eq!(Sum::part2(&input), 24);
+/// ```
+pub trait Puzzle {
+    type Input;
+    type Output1: fmt::Debug + fmt::Display + PartialEq;
+    type Output2: fmt::Debug + fmt::Display + PartialEq;
+    type ParseError: fmt::Display;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::ParseError>;
+
+    fn part1(input: &Self::Input) -> Self::Output1;
+
+    fn part2(input: &Self::Input) -> Self::Output2;
+}
+
+/// Read and parse an input file, panicking with a readable message if either step fails.
+///
+/// Meant for tests and benchmarks, where a missing or malformed input file is a bug.
+pub fn read_and_parse<P: Puzzle>(file_path: impl AsRef<Path>) -> P::Input {
+    let file_path = file_path.as_ref();
+    let input = read_input(file_path).unwrap_or_else(|err| panic!("Error reading {}: {}", file_path.display(), err));
+    P::parse(&input).unwrap_or_else(|err| panic!("Error parsing {}: {}", file_path.display(), err))
+}
+
+pub fn read_and_solve_part1<P: Puzzle>(file_path: impl AsRef<Path>) -> P::Output1 {
+    P::part1(&read_and_parse::<P>(file_path))
+}
+
+pub fn read_and_solve_part2<P: Puzzle>(file_path: impl AsRef<Path>) -> P::Output2 {
+    P::part2(&read_and_parse::<P>(file_path))
+}
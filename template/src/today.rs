@@ -1,6 +1,12 @@
+// Template for a new day. Copy this file into `dayN/src/today.rs`, replace `crate::` by `aoc::`, rename `Today` to `DayN`,
+// register the day in the runner and remove the `allow` and `ignore` attributes once the placeholders are filled in.
+#![allow(dead_code, unused_variables, clippy::match_single_binding)]
+
 use std::fmt;
 use std::str::FromStr;
 
+use crate::error::ParseError;
+use crate::puzzle::Puzzle;
 // use crate::aux;
 
 
@@ -8,6 +14,28 @@ pub type Solution1 = u64;
 pub type Solution2 = u64;
 
 
+pub struct Today;
+
+impl Puzzle for Today {
+    type Input = PuzzleInput;
+    type Output1 = Solution1;
+    type Output2 = Solution2;
+    type ParseError = <PuzzleInput as FromStr>::Err;
+
+    fn parse(input: &str) -> Result<PuzzleInput, Self::ParseError> {
+        input.parse()
+    }
+
+    fn part1(input: &PuzzleInput) -> Solution1 {
+        solve_part1(input)
+    }
+
+    fn part2(input: &PuzzleInput) -> Solution2 {
+        solve_part2(input)
+    }
+}
+
+
 pub fn solve_part1(input: &PuzzleInput) -> Solution1 {
     todo!()
 }
@@ -22,7 +50,7 @@ pub struct PuzzleInput {
 }
 
 impl FromStr for PuzzleInput {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<PuzzleInput, Self::Err> {
         todo!()
@@ -36,9 +64,11 @@ enum Symbol {
 }
 
 impl Symbol {
-    fn from_char(c: char) -> Result<Symbol, String> {
+    const EXPECTED: &'static str = "a map symbol";
+
+    fn from_char(c: char) -> Option<Symbol> {
         match c {
-            _ => Err(format!("Invalid character: {}", c))
+            _ => None
         }
     }
 }
@@ -61,3 +91,52 @@ impl fmt::Display for Symbol {
         }
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use crate::aux::{INPUT_FILE, TEST_INPUT_FILE};
+    use crate::puzzle::{read_and_solve_part1, read_and_solve_part2};
+    use super::*;
+
+    const TEST_SOLUTION_1: Solution1 = 0;
+    const TEST_SOLUTION_2: Solution2 = 0;
+
+    #[test]
+    #[ignore = "template"]
+    fn test_part1() {
+        let test_output = read_and_solve_part1::<Today>(TEST_INPUT_FILE);
+        assert_eq!(test_output, TEST_SOLUTION_1);
+    }
+
+    #[test]
+    #[ignore = "template"]
+    fn test_part2() {
+        let test_output = read_and_solve_part2::<Today>(TEST_INPUT_FILE);
+        assert_eq!(test_output, TEST_SOLUTION_2);
+    }
+
+    // const TEST_1 = todo!();
+    // const TEST_2 = todo!();
+
+    // #[test]
+    // fn test_cases() {
+    //     let test_input = crate::puzzle::read_and_parse::<Today>(TEST_INPUT_FILE);
+    //     let output_1 = solve_single(&test_input.cases[0]);
+    //     let output_2 = solve_single(&test_input.cases[1]);
+    //     assert_eq!(output_1, TEST_1);
+    //     assert_eq!(output_2, TEST_2);
+    // }
+
+    #[bench]
+    #[ignore = "template"]
+    fn bench_part1(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part1::<Today>(INPUT_FILE));
+    }
+
+    #[bench]
+    #[ignore = "template"]
+    fn bench_part2(b: &mut test::Bencher) {
+        b.iter(|| read_and_solve_part2::<Today>(INPUT_FILE));
+    }
+}